@@ -0,0 +1,75 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use wallet_bk_recovery::{
+    bip32::{scan_addresses, ExtendedPrivKey},
+    Bip39Generator, Language, Network,
+};
+
+// The mnemonic of the first Trezor test vector
+const MNEMONIC: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+// The English wordlist as a file, to measure reading it from disk
+const WORDLIST_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/english.txt");
+
+// Generating a mnemonic. Every generation starts afresh so one
+// generator is reused, with its wordlist already split into words.
+fn generation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generation");
+    let mut generator = Bip39Generator::with_language(Language::English);
+
+    group.bench_function("mnemonic::<16>", |b| {
+        b.iter(|| generator.mnemonic::<16>().unwrap())
+    });
+    group.bench_function("mnemonic::<32>", |b| {
+        b.iter(|| generator.mnemonic::<32>().unwrap())
+    });
+
+    group.finish();
+}
+
+// PBKDF2 with the 2048 iterations of BIP39 dominates the time
+// spent generating or recovering, a regression shows up here
+fn seed(c: &mut Criterion) {
+    c.bench_function("seed", |b| {
+        b.iter(|| Bip39Generator::seed(black_box(MNEMONIC), black_box(Some("TREZOR"))).unwrap())
+    });
+}
+
+// Looking words up needs the wordlist. A generator only reads
+// it the first time, so we compare a generator that already
+// has it cached against one that has to read the file again.
+fn wordlist(c: &mut Criterion) {
+    let mut group = c.benchmark_group("load_wordlist");
+
+    let cached = Bip39Generator::new(WORDLIST_PATH);
+    cached.validate_mnemonic(MNEMONIC).unwrap();
+    group.bench_function("cached", |b| {
+        b.iter(|| cached.validate_mnemonic(black_box(MNEMONIC)).unwrap())
+    });
+
+    group.bench_function("from disk", |b| {
+        b.iter(|| {
+            Bip39Generator::new(WORDLIST_PATH)
+                .validate_mnemonic(black_box(MNEMONIC))
+                .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+// Deriving the first 1000 receive addresses of an account, run
+// with and without `--features parallel` to compare rayon with
+// the sequential path
+fn address_scan(c: &mut Criterion) {
+    let seed = Bip39Generator::seed(MNEMONIC, None).unwrap();
+    let root = ExtendedPrivKey::new_master(&seed).unwrap();
+
+    c.bench_function("scan_addresses 1000", |b| {
+        b.iter(|| scan_addresses(&root, 0, black_box(1000), Network::Mainnet).unwrap())
+    });
+}
+
+criterion_group!(benches, generation, seed, wordlist, address_scan);
+criterion_main!(benches);
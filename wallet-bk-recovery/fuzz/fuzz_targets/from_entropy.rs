@@ -0,0 +1,26 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use wallet_bk_recovery::{Bip39Generator, Language, ENTROPY_SIZES};
+
+// Every BIP39 entropy size must give a valid mnemonic that turns
+// back into the same bytes, any other size must be rejected. A
+// single generator is reused so stale state from the previous
+// input would show up too.
+fuzz_target!(|data: &[u8]| {
+    let mut generator = Bip39Generator::with_language(Language::English);
+
+    for entropy in [data, &data[..data.len().min(32)]] {
+        match generator.from_entropy(entropy) {
+            Ok(mnemonic) => {
+                assert!(ENTROPY_SIZES.contains(&entropy.len()));
+                assert_eq!(mnemonic.word_count(), entropy.len() * 3 / 4);
+
+                let sentence = mnemonic.to_string();
+                assert!(generator.validate_mnemonic(&sentence).unwrap());
+                assert_eq!(generator.mnemonic_to_entropy(&sentence).unwrap(), entropy);
+            }
+            Err(_) => assert!(!ENTROPY_SIZES.contains(&entropy.len())),
+        }
+    }
+});
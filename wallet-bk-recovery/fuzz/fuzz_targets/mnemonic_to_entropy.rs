@@ -0,0 +1,24 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use wallet_bk_recovery::{Bip39Generator, Language};
+
+// Any string must either be rejected or turn into entropy, never
+// panic. The first byte picks the wordlist so every language gets
+// fuzzed. Entropy we accept must give back a mnemonic that turns
+// into the same entropy again.
+fuzz_target!(|data: &[u8]| {
+    let Some((&language, sentence)) = data.split_first() else {
+        return;
+    };
+    let Ok(sentence) = std::str::from_utf8(sentence) else {
+        return;
+    };
+    let language = Language::ALL[language as usize % Language::ALL.len()];
+
+    let mut generator = Bip39Generator::with_language(language);
+    if let Ok(entropy) = generator.mnemonic_to_entropy(sentence) {
+        let mnemonic = generator.from_entropy(&entropy).unwrap().to_string();
+        assert_eq!(generator.mnemonic_to_entropy(&mnemonic).unwrap(), entropy);
+    }
+});
@@ -0,0 +1,21 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use wallet_bk_recovery::{Bip39Generator, Language};
+
+// Validating never fails on the embedded wordlists, it only says
+// whether the words are a mnemonic, and it has to agree with
+// `mnemonic_to_entropy()` and parsing a `Mnemonic`.
+fuzz_target!(|data: &[u8]| {
+    let Ok(sentence) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let generator = Bip39Generator::with_language(Language::English);
+    let valid = generator.validate_mnemonic(sentence).unwrap();
+
+    assert_eq!(valid, generator.mnemonic_to_entropy(sentence).is_ok());
+    if valid {
+        assert!(sentence.parse::<wallet_bk_recovery::Mnemonic>().is_ok());
+    }
+});
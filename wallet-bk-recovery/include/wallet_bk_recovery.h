@@ -0,0 +1,23 @@
+/* C bindings of wallet-bk-recovery, built with `cargo build --release --features ffi` */
+#ifndef WALLET_BK_RECOVERY_H
+#define WALLET_BK_RECOVERY_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define FFI_OK 0
+#define FFI_INVALID_WORD_COUNT -1
+#define FFI_NULL_POINTER -2
+#define FFI_BUFFER_TOO_SMALL -3
+#define FFI_GENERATION_FAILED -4
+
+/*
+ * Generates an English mnemonic of 12, 15, 18, 21 or 24 words and
+ * writes it to `out` as a NUL terminated UTF-8 string. `*out_len`
+ * is the size of `out` on input and the length of the mnemonic
+ * (without the NUL) on success. With FFI_BUFFER_TOO_SMALL it holds
+ * the size needed instead. Wipe `out` once done with the words.
+ */
+int32_t generate_c(uint32_t word_count, uint8_t *out, size_t *out_len);
+
+#endif
@@ -0,0 +1,1025 @@
+use std::{collections::BTreeMap, fmt, ops::Range};
+
+use bech32::{segwit, Hrp};
+use hmac::{Hmac, Mac};
+use k256::{
+    elliptic_curve::{sec1::ToEncodedPoint, PrimeField},
+    ProjectivePoint, Scalar, SecretKey,
+};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::{Bip39Error, Network};
+
+// The key BIP32 uses for the HMAC that turns a seed into the master key
+pub const MASTER_HMAC_KEY: &[u8] = b"Bitcoin seed";
+// Child indexes from 2^31 onwards are hardened, in a path
+// we write them as the index followed by a `'` e.g. `44'`
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+// The version bytes that prefix a serialized extended key,
+// they are what makes the base58 string start with
+// `xprv`/`xpub` on mainnet and `tprv`/`tpub` on testnet
+pub const MAINNET_XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xAD, 0xE4];
+pub const MAINNET_XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xB2, 0x1E];
+pub const TESTNET_XPRV_VERSION: [u8; 4] = [0x04, 0x35, 0x83, 0x94];
+pub const TESTNET_XPUB_VERSION: [u8; 4] = [0x04, 0x35, 0x87, 0xCF];
+
+// SLIP-0132 gives account keys of other script types their
+// own version bytes so a watch-only wallet knows which
+// addresses to derive: `ypub`/`upub` for nested segwit
+// (BIP49) and `zpub`/`vpub` for native segwit (BIP84)
+pub const MAINNET_YPUB_VERSION: [u8; 4] = [0x04, 0x9D, 0x7C, 0xB2];
+pub const MAINNET_ZPUB_VERSION: [u8; 4] = [0x04, 0xB2, 0x47, 0x46];
+pub const TESTNET_UPUB_VERSION: [u8; 4] = [0x04, 0x4A, 0x52, 0x62];
+pub const TESTNET_VPUB_VERSION: [u8; 4] = [0x04, 0x5F, 0x1C, 0xF6];
+
+// The human readable part that starts bech32 segwit
+// addresses, `bc1...` on mainnet, `tb1...` on testnet
+// and `bcrt1...` on regtest
+pub const MAINNET_HRP: &str = "bc";
+pub const TESTNET_HRP: &str = "tb";
+pub const REGTEST_HRP: &str = "bcrt";
+
+// The tag of the hash BIP341 tweaks Taproot keys with
+pub const TAP_TWEAK_TAG: &[u8] = b"TapTweak";
+
+// This function computes RIPEMD160(SHA256(data)), the hash
+// Bitcoin uses for key fingerprints and addresses
+pub(crate) fn hash160(data: &[u8]) -> [u8; 20] {
+    let sha256 = Sha256::digest(data);
+    Ripemd160::digest(sha256).into()
+}
+
+// How a public key is serialized. Compressed keys are 33 bytes,
+// a `0x02` or `0x03` byte telling whether y is even or odd followed
+// by x. Uncompressed keys are 65 bytes, `0x04` followed by x and y.
+// Modern wallets and segwit only use compressed keys, uncompressed
+// ones are for the legacy addresses of very old wallets. The same
+// private key gives a different P2PKH address in each format, so
+// importing a key needs the format it was used with to find its funds.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyFormat {
+    #[default]
+    Compressed,
+    Uncompressed,
+}
+
+impl KeyFormat {
+    // Whether the public key is the 33 byte compressed one
+    pub fn is_compressed(&self) -> bool {
+        *self == KeyFormat::Compressed
+    }
+}
+
+// The standard kinds of single key addresses and the BIP that
+// lays out the derivation paths of each, so a wallet finds the
+// same addresses as any other wallet following it:
+// Legacy `1...` addresses (BIP44), nested segwit `3...`
+// addresses (BIP49), native segwit `bc1q...` addresses (BIP84)
+// and taproot `bc1p...` addresses (BIP86).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddressType {
+    Legacy,
+    NestedSegwit,
+    NativeSegwit,
+    Taproot,
+}
+
+impl AddressType {
+    // The purpose, the first hardened level of the path, which
+    // is the number of the BIP that defines the address type
+    pub fn purpose(&self) -> u32 {
+        match self {
+            AddressType::Legacy => 44,
+            AddressType::NestedSegwit => 49,
+            AddressType::NativeSegwit => 84,
+            AddressType::Taproot => 86,
+        }
+    }
+
+    // The canonical path of an address on mainnet, e.g.
+    // `m/84'/0'/0'/0/5` for the sixth native segwit receive
+    // address of the first account, ready for `derive_path()`.
+    // Change addresses, the ones a wallet sends the rest of a
+    // payment back to, use `1` instead of `0` on the fourth level.
+    // The coin type is `0'` for Bitcoin, testnet wallets put `1'`
+    // there instead.
+    pub fn default_path(&self, account: u32, change: bool, index: u32) -> String {
+        format!(
+            "m/{}'/0'/{}'/{}/{}",
+            self.purpose(),
+            account,
+            u32::from(change),
+            index
+        )
+    }
+}
+
+// An extended private key is a private key together with
+// a chain code. The chain code is the extra 256 bits of
+// entropy that lets us derive child keys from this key.
+// Both are wiped from memory when the key is dropped.
+#[derive(Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct ExtendedPrivKey {
+    // How many derivations away from the master key we are
+    depth: u8,
+    // The first 4 bytes of the hash of our parent's public
+    // key, all zeros for the master key
+    parent_fingerprint: [u8; 4],
+    // The index we were derived at, zero for the master key
+    child_number: u32,
+    private_key: [u8; 32],
+    chain_code: [u8; 32],
+}
+
+impl ExtendedPrivKey {
+    // This method derives the master key from the seed we
+    // get out of `Bip39Generator::seed()` as described in BIP32
+    pub fn new_master(seed: &[u8]) -> Result<ExtendedPrivKey, Bip39Error> {
+        // BIP32 seeds are between 128 and 512 bits, our
+        // BIP39 seeds are always the full 512 bits
+        if !(16..=64).contains(&seed.len()) {
+            return Err(Bip39Error::InvalidSeedLength(seed.len()));
+        }
+
+        // Compute HMAC-SHA512 with the key "Bitcoin seed" over our seed
+        let mut mac = Hmac::<Sha512>::new_from_slice(MASTER_HMAC_KEY)
+            .expect("HMAC accepts keys of any length");
+        mac.update(seed);
+        let output = mac.finalize().into_bytes();
+
+        // The left 32 bytes are our master private key
+        // and the right 32 bytes are our master chain code
+        let mut private_key = [0u8; 32];
+        let mut chain_code = [0u8; 32];
+        private_key.copy_from_slice(&output[..32]);
+        chain_code.copy_from_slice(&output[32..]);
+
+        // The private key must be a valid secp256k1 scalar,
+        // that is not zero and less than the curve order
+        SecretKey::from_slice(&private_key).map_err(|_| Bip39Error::InvalidPrivateKey)?;
+
+        Ok(ExtendedPrivKey {
+            depth: 0,
+            parent_fingerprint: [0u8; 4],
+            child_number: 0,
+            private_key,
+            chain_code,
+        })
+    }
+
+    // This method parses an `xprv` or `tprv` string like the ones
+    // `to_xprv()` gives us, so we can keep deriving from a key we
+    // exported earlier without its seed. The string must Base58Check
+    // decode to the 78 bytes BIP32 lays out: the version, depth,
+    // parent fingerprint, child number, chain code and a zero
+    // byte followed by the private key. A master key (depth 0)
+    // has no parent so its fingerprint and child number must be
+    // zero. Extended public keys are private keys we can't
+    // recover and are rejected by their version.
+    pub fn from_xprv(xprv: &str) -> Result<ExtendedPrivKey, Bip39Error> {
+        let data = Zeroizing::new(
+            bs58::decode(xprv)
+                .with_check(None)
+                .into_vec()
+                .map_err(|error| Bip39Error::InvalidExtendedKey(error.to_string()))?,
+        );
+
+        if data.len() != 78 {
+            return Err(Bip39Error::InvalidExtendedKey(format!(
+                "expected 78 bytes but found {}",
+                data.len()
+            )));
+        }
+
+        let version = &data[..4];
+        if version != MAINNET_XPRV_VERSION && version != TESTNET_XPRV_VERSION {
+            return Err(Bip39Error::InvalidExtendedKey(format!(
+                "unknown version {}, expected an xprv or tprv",
+                hex::encode(version)
+            )));
+        }
+
+        let depth = data[4];
+        let mut parent_fingerprint = [0u8; 4];
+        parent_fingerprint.copy_from_slice(&data[5..9]);
+        let child_number = u32::from_be_bytes([data[9], data[10], data[11], data[12]]);
+
+        if depth == 0 && (parent_fingerprint != [0u8; 4] || child_number != 0) {
+            return Err(Bip39Error::InvalidExtendedKey(
+                "a master key has no parent".to_owned(),
+            ));
+        }
+
+        // Private keys are serialized as a zero byte followed by
+        // the key, public keys start with 0x02 or 0x03 instead
+        if data[45] != 0x00 {
+            return Err(Bip39Error::InvalidExtendedKey(
+                "expected a private key".to_owned(),
+            ));
+        }
+
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(&data[13..45]);
+        let mut private_key = [0u8; 32];
+        private_key.copy_from_slice(&data[46..]);
+
+        // The key must be a valid secp256k1 scalar, not zero
+        // and below the curve order
+        if SecretKey::from_slice(&private_key).is_err() {
+            private_key.zeroize();
+            return Err(Bip39Error::InvalidPrivateKey);
+        }
+
+        Ok(ExtendedPrivKey {
+            depth,
+            parent_fingerprint,
+            child_number,
+            private_key,
+            chain_code,
+        })
+    }
+
+    // The number of derivations from the master key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    // The fingerprint of the parent key
+    pub fn parent_fingerprint(&self) -> &[u8; 4] {
+        &self.parent_fingerprint
+    }
+
+    // The index this key was derived at
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    // The 32 byte private key
+    pub fn private_key(&self) -> &[u8; 32] {
+        &self.private_key
+    }
+
+    // The 32 byte chain code
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    // This method returns the 33 byte compressed public key
+    // that belongs to our private key
+    pub fn public_key(&self) -> [u8; 33] {
+        let secret_key =
+            SecretKey::from_slice(&self.private_key).expect("private key is always valid");
+        let encoded = secret_key.public_key().to_encoded_point(true);
+
+        let mut public_key = [0u8; 33];
+        public_key.copy_from_slice(encoded.as_bytes());
+
+        public_key
+    }
+
+    // Same as `public_key()` but in the format we ask for, giving
+    // the 65 byte uncompressed key with `KeyFormat::Uncompressed`
+    pub fn public_key_with_format(&self, format: KeyFormat) -> Vec<u8> {
+        let secret_key =
+            SecretKey::from_slice(&self.private_key).expect("private key is always valid");
+
+        secret_key
+            .public_key()
+            .to_encoded_point(format.is_compressed())
+            .as_bytes()
+            .to_vec()
+    }
+
+    // This method returns the 32 byte x-only public key BIP340
+    // uses for Taproot, the x coordinate of our public key. The
+    // y coordinate is implied to be even which is what the
+    // leading byte of the compressed key would tell us.
+    pub fn x_only_public_key(&self) -> [u8; 32] {
+        let mut x_only = [0u8; 32];
+        x_only.copy_from_slice(&self.public_key()[1..]);
+
+        x_only
+    }
+
+    // The fingerprint of this key is the first 4 bytes of the
+    // HASH160 of its public key, children store it to point
+    // back at their parent
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let mut fingerprint = [0u8; 4];
+        fingerprint.copy_from_slice(&hash160(&self.public_key())[..4]);
+
+        fingerprint
+    }
+
+    // This method derives the child key at `index` following
+    // the private parent key -> private child key function
+    // of BIP32. Indexes of `HARDENED_OFFSET` and above give us
+    // hardened children which can't be derived from the public key.
+    pub fn derive_child(&self, index: u32) -> Result<ExtendedPrivKey, Bip39Error> {
+        // The depth is serialized as a single byte so we
+        // can't go deeper than 255 derivations
+        let depth = self.depth.checked_add(1).ok_or(Bip39Error::MaxDepthExceeded)?;
+
+        let mut mac = Hmac::<Sha512>::new_from_slice(&self.chain_code)
+            .expect("HMAC accepts keys of any length");
+
+        // Hardened children hash the private key (prefixed with a
+        // zero byte to make it 33 bytes long) while normal children
+        // hash the compressed public key
+        if index >= HARDENED_OFFSET {
+            mac.update(&[0u8]);
+            mac.update(&self.private_key);
+        } else {
+            mac.update(&self.public_key());
+        }
+        mac.update(&index.to_be_bytes());
+        let output = mac.finalize().into_bytes();
+
+        // The left 32 bytes are added to our private key
+        // and the right 32 bytes become the child chain code
+        let mut tweak = [0u8; 32];
+        let mut chain_code = [0u8; 32];
+        tweak.copy_from_slice(&output[..32]);
+        chain_code.copy_from_slice(&output[32..]);
+
+        // The tweak must be less than the curve order and the
+        // resulting key must not be zero, otherwise BIP32 tells
+        // us the key at this index is invalid
+        let tweak = Option::<Scalar>::from(Scalar::from_repr(tweak.into()))
+            .ok_or(Bip39Error::InvalidPrivateKey)?;
+        let parent = Option::<Scalar>::from(Scalar::from_repr(self.private_key.into()))
+            .ok_or(Bip39Error::InvalidPrivateKey)?;
+        let child = tweak + parent;
+
+        if bool::from(child.is_zero()) {
+            return Err(Bip39Error::InvalidPrivateKey);
+        }
+
+        Ok(ExtendedPrivKey {
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: index,
+            private_key: child.to_repr().into(),
+            chain_code,
+        })
+    }
+
+    // This method walks a derivation path such as `m/44'/0'/0'/0/0`
+    // starting from our key, deriving one child for each
+    // component. A trailing `'` (or `h`) marks a hardened index.
+    pub fn derive_path(&self, path: &str) -> Result<ExtendedPrivKey, Bip39Error> {
+        let mut components = path.split('/');
+
+        // Every path starts at the master key `m`
+        if components.next() != Some("m") {
+            return Err(Bip39Error::InvalidDerivationPath(path.to_owned()));
+        }
+
+        let mut key = self.clone();
+
+        for component in components {
+            // Split off the hardened marker if there is one
+            let (number, hardened) = match component
+                .strip_suffix('\'')
+                .or_else(|| component.strip_suffix('h'))
+            {
+                Some(number) => (number, true),
+                None => (component, false),
+            };
+
+            // Only plain digits are allowed, `parse()` would
+            // otherwise also accept a leading `+`
+            if number.is_empty() || !number.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(Bip39Error::InvalidDerivationPath(path.to_owned()));
+            }
+
+            // The index itself must fit in 31 bits, the top
+            // bit is reserved for marking hardened children
+            let index = number
+                .parse::<u32>()
+                .ok()
+                .filter(|index| *index < HARDENED_OFFSET)
+                .ok_or_else(|| Bip39Error::InvalidDerivationPath(path.to_owned()))?;
+
+            key = if hardened {
+                key.derive_child(index + HARDENED_OFFSET)?
+            } else {
+                key.derive_child(index)?
+            };
+        }
+
+        Ok(key)
+    }
+
+    // This method serializes our key as an `xprv` string on
+    // mainnet or a `tprv` string on testnet and regtest
+    pub fn to_xprv(&self, network: Network) -> String {
+        self.to_xprv_with_version(network.xprv_version())
+    }
+
+    // This method serializes our public key as an `xpub` string
+    // on mainnet or a `tpub` string on testnet and regtest
+    pub fn to_xpub(&self, network: Network) -> String {
+        self.to_xpub_with_version(network.xpub_version())
+    }
+
+    // This method derives the account key `m/purpose'/coin'/account'`
+    // and serializes its public key for a watch-only wallet,
+    // which can derive every address of the account from it but
+    // can't spend. The version bytes follow the purpose: `xpub`
+    // for legacy (44), `ypub` for nested segwit (49) and `zpub`
+    // for native segwit (84) or `tpub`, `upub` and `vpub` off
+    // mainnet. We call this on the master key.
+    pub fn account_xpub(
+        &self,
+        purpose: u32,
+        coin: u32,
+        account: u32,
+        network: Network,
+    ) -> Result<String, Bip39Error> {
+        let path = format!("m/{}'/{}'/{}'", purpose, coin, account);
+
+        let version = match account_xpub_version(purpose, network) {
+            Some(version) => version,
+            None => return Err(Bip39Error::InvalidDerivationPath(path)),
+        };
+
+        Ok(self.derive_path(&path)?.to_xpub_with_version(version))
+    }
+
+    // Same as `account_xpub()` for every account in `accounts`,
+    // e.g. `0..5` for the first five, mapped from the account
+    // index to its xpub. The keys up to `m/purpose'/coin'` are the
+    // same for every account so we only derive them once.
+    pub fn account_xpubs(
+        &self,
+        purpose: u32,
+        coin: u32,
+        accounts: Range<u32>,
+        network: Network,
+    ) -> Result<BTreeMap<u32, String>, Bip39Error> {
+        let path = format!("m/{}'/{}'", purpose, coin);
+
+        let version = match account_xpub_version(purpose, network) {
+            Some(version) => version,
+            None => return Err(Bip39Error::InvalidDerivationPath(path)),
+        };
+
+        let coin_key = self.derive_path(&path)?;
+
+        accounts
+            .map(|account| {
+                // Accounts are hardened so the index must leave
+                // the top bit free for marking them
+                if account >= HARDENED_OFFSET {
+                    return Err(Bip39Error::InvalidDerivationPath(format!(
+                        "{}/{}'",
+                        path, account
+                    )));
+                }
+
+                let key = coin_key.derive_child(account + HARDENED_OFFSET)?;
+
+                Ok((account, key.to_xpub_with_version(version)))
+            })
+            .collect()
+    }
+
+    // Same as `to_xprv()` but with any version bytes we want,
+    // e.g. `TESTNET_XPRV_VERSION` for `tprv`. Private keys are
+    // serialized as a zero byte followed by the 32 bytes of the key.
+    pub fn to_xprv_with_version(&self, version: [u8; 4]) -> String {
+        let mut key = [0u8; 33];
+        key[1..].copy_from_slice(&self.private_key);
+
+        self.serialize(version, &key)
+    }
+
+    // Same as `to_xpub()` but with the version bytes of the
+    // network we want, e.g. `TESTNET_XPUB_VERSION` for `tpub`
+    pub fn to_xpub_with_version(&self, version: [u8; 4]) -> String {
+        self.serialize(version, &self.public_key())
+    }
+
+    // This method builds the 78 byte serialization described
+    // in BIP32 and encodes it using Base58Check, which appends
+    // the first 4 bytes of a double SHA256 as a checksum
+    fn serialize(&self, version: [u8; 4], key: &[u8; 33]) -> String {
+        let mut data = Vec::<u8>::with_capacity(78);
+        data.extend_from_slice(&version); // 4 bytes
+        data.push(self.depth); // 1 byte
+        data.extend_from_slice(&self.parent_fingerprint); // 4 bytes
+        data.extend_from_slice(&self.child_number.to_be_bytes()); // 4 bytes
+        data.extend_from_slice(&self.chain_code); // 32 bytes
+        data.extend_from_slice(key); // 33 bytes
+
+        bs58::encode(data).with_check().into_string()
+    }
+
+    // This method encodes our private key in the Wallet Import
+    // Format that Bitcoin Core's `importprivkey` understands.
+    // The key is prefixed with the WIF byte of the network and
+    // followed by `0x01` when the key should be used with its
+    // compressed public key, then Base58Check encoded. The
+    // `format` has to match the one the key's addresses were made
+    // with or the imported key won't find its funds.
+    pub fn to_wif(&self, network: Network, format: KeyFormat) -> String {
+        let mut data = Zeroizing::new(Vec::<u8>::with_capacity(34));
+        data.push(network.wif_prefix()); // 1 byte
+        data.extend_from_slice(&self.private_key); // 32 bytes
+        if format.is_compressed() {
+            data.push(0x01); // 1 byte
+        }
+
+        bs58::encode(data.as_slice()).with_check().into_string()
+    }
+
+    // This method gives us a native segwit (P2WPKH) address
+    // for our key. The witness program is the HASH160 of our
+    // compressed public key which is bech32 encoded with
+    // witness version 0 using the hrp of our network, giving
+    // `bc1...`, `tb1...` or `bcrt1...` addresses.
+    pub fn p2wpkh_address(&self, network: Network) -> Result<String, Bip39Error> {
+        self.p2wpkh_address_with_hrp(network.hrp())
+    }
+
+    // This method gives us a Taproot (P2TR) address for our key
+    // that can only be spent with the key path, as BIP86 does it.
+    // Our public key is the internal key, negated if needed so its
+    // y coordinate is even, and we tweak it by adding `t * G` where
+    // `t` is the tagged hash "TapTweak" of its x coordinate. The x
+    // coordinate of the result is bech32m encoded with witness
+    // version 1, giving `bc1p...` addresses on mainnet.
+    pub fn p2tr_address(&self, network: Network) -> Result<String, Bip39Error> {
+        let hrp = Hrp::parse(network.hrp())
+            .map_err(|_| Bip39Error::InvalidHrp(network.hrp().to_owned()))?;
+
+        let secret_key =
+            SecretKey::from_slice(&self.private_key).expect("private key is always valid");
+        let point = secret_key.public_key().to_projective();
+        let internal_key = if self.public_key()[0] == 0x02 {
+            point
+        } else {
+            -point
+        };
+
+        // A tagged hash is SHA256(SHA256(tag) || SHA256(tag) || data)
+        let tag = Sha256::digest(TAP_TWEAK_TAG);
+        let tweak: [u8; 32] = Sha256::new()
+            .chain_update(tag)
+            .chain_update(tag)
+            .chain_update(self.x_only_public_key())
+            .finalize()
+            .into();
+
+        // Like BIP32 tweaks the tweak has to be less than the curve
+        // order and must not take us to the point at infinity
+        let tweak = Option::<Scalar>::from(Scalar::from_repr(tweak.into()))
+            .ok_or(Bip39Error::InvalidPrivateKey)?;
+        let output_key = internal_key + ProjectivePoint::GENERATOR * tweak;
+        if output_key == ProjectivePoint::IDENTITY {
+            return Err(Bip39Error::InvalidPrivateKey);
+        }
+
+        let encoded = output_key.to_affine().to_encoded_point(true);
+        let program = encoded.x().expect("the output key is not the point at infinity");
+
+        segwit::encode(hrp, segwit::VERSION_1, program)
+            .map_err(|error| Bip39Error::AddressEncoding(error.to_string()))
+    }
+
+    // This method gives us a legacy (P2PKH) address for our key,
+    // the `1...` addresses of the first Bitcoin wallets. We
+    // Base58Check encode the HASH160 of our public key after the
+    // P2PKH prefix of our network. Old wallets hashed the
+    // uncompressed key, so their funds are only found with
+    // `KeyFormat::Uncompressed`.
+    pub fn p2pkh_address(&self, network: Network, format: KeyFormat) -> Result<String, Bip39Error> {
+        let mut data = Vec::<u8>::with_capacity(21);
+        data.push(network.p2pkh_prefix()); // 1 byte
+        data.extend_from_slice(&hash160(&self.public_key_with_format(format))); // 20 bytes
+
+        Ok(bs58::encode(data).with_check().into_string())
+    }
+
+    // This method gives us a nested segwit (P2SH-P2WPKH) address
+    // for our key as BIP49 describes it, for services that only
+    // accept `3...` addresses. The redeem script is the witness
+    // program of `p2wpkh_address()`, a zero byte (witness version
+    // 0) followed by the 20 byte HASH160 of our public key. We
+    // Base58Check encode the HASH160 of that script after the
+    // P2SH prefix of our network.
+    pub fn p2sh_p2wpkh_address(&self, network: Network) -> Result<String, Bip39Error> {
+        let mut redeem_script = Vec::<u8>::with_capacity(22);
+        redeem_script.push(0x00); // OP_0
+        redeem_script.push(0x14); // push the next 20 bytes
+        redeem_script.extend_from_slice(&hash160(&self.public_key()));
+
+        let mut data = Vec::<u8>::with_capacity(21);
+        data.push(network.p2sh_prefix()); // 1 byte
+        data.extend_from_slice(&hash160(&redeem_script)); // 20 bytes
+
+        Ok(bs58::encode(data).with_check().into_string())
+    }
+
+    // Same as `p2wpkh_address()` but with any hrp we want
+    pub fn p2wpkh_address_with_hrp(&self, hrp: &str) -> Result<String, Bip39Error> {
+        let hrp = Hrp::parse(hrp).map_err(|_| Bip39Error::InvalidHrp(hrp.to_owned()))?;
+        let program = hash160(&self.public_key());
+
+        segwit::encode(hrp, segwit::VERSION_0, &program)
+            .map_err(|error| Bip39Error::AddressEncoding(error.to_string()))
+    }
+}
+
+// Debug output shows where the key sits in the tree but never the
+// private key or chain code, either of which puts funds at risk
+impl fmt::Debug for ExtendedPrivKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedPrivKey")
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &hex::encode(self.parent_fingerprint))
+            .field("child_number", &self.child_number)
+            .field("private_key", &format_args!("<redacted>"))
+            .field("chain_code", &format_args!("<redacted>"))
+            .finish()
+    }
+}
+
+// The version bytes of the account xpubs of `purpose`, or `None`
+// for a purpose we don't know the version bytes of
+fn account_xpub_version(purpose: u32, network: Network) -> Option<[u8; 4]> {
+    match (purpose, network) {
+        (44, network) => Some(network.xpub_version()),
+        (49, Network::Mainnet) => Some(MAINNET_YPUB_VERSION),
+        (49, Network::Testnet | Network::Regtest) => Some(TESTNET_UPUB_VERSION),
+        (84, Network::Mainnet) => Some(MAINNET_ZPUB_VERSION),
+        (84, Network::Testnet | Network::Regtest) => Some(TESTNET_VPUB_VERSION),
+        _ => None,
+    }
+}
+
+// This function derives the first `count` native segwit receive
+// addresses of an account following BIP84, that is the keys at
+// `m/84'/coin'/account'/0/0` up to `m/84'/coin'/account'/0/{count - 1}`
+// with the coin type of `network`, `0'` on mainnet and `1'` on
+// testnet and regtest. That is what a wallet needs to look up
+// which addresses have been used. Each address costs a couple of
+// secp256k1 point multiplications, so with the `parallel` feature
+// the addresses are derived on all cores using rayon.
+pub fn scan_addresses(
+    root: &ExtendedPrivKey,
+    account: u32,
+    count: u32,
+    network: Network,
+) -> Result<Vec<String>, Bip39Error> {
+    // The hardened part of the path is the same for every
+    // address so we only derive it once
+    let path = format!("m/84'/{}'/{}'/0", network.coin_type(), account);
+    let receive = root.derive_path(&path)?;
+
+    let address = |index: u32| receive.derive_child(index)?.p2wpkh_address(network);
+
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+
+        (0..count).into_par_iter().map(address).collect()
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        (0..count).map(address).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example key from the Bitcoin wiki page on WIF
+    const PRIVATE_KEY: &str = "0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d";
+
+    fn key() -> ExtendedPrivKey {
+        let mut private_key = [0u8; 32];
+        hex::decode_to_slice(PRIVATE_KEY, &mut private_key).unwrap();
+
+        ExtendedPrivKey {
+            depth: 0,
+            parent_fingerprint: [0u8; 4],
+            child_number: 0,
+            private_key,
+            chain_code: [0u8; 32],
+        }
+    }
+
+    // The master key of "abandon ... about" without a passphrase,
+    // the mnemonic of the BIP49, BIP84 and BIP86 test vectors
+    fn abandon_master() -> ExtendedPrivKey {
+        let seed = crate::Bip39Generator::seed(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            None,
+        )
+        .unwrap();
+
+        ExtendedPrivKey::new_master(&seed).unwrap()
+    }
+
+    #[test]
+    fn scan_addresses_matches_derive_path() {
+        let master = abandon_master();
+
+        let addresses = scan_addresses(&master, 0, 5, Network::Mainnet).unwrap();
+        assert_eq!(addresses.len(), 5);
+        // The first receive address of the BIP84 test vector
+        assert_eq!(addresses[0], "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu");
+        for (index, address) in addresses.iter().enumerate() {
+            let key = master.derive_path(&format!("m/84'/0'/0'/0/{}", index)).unwrap();
+            assert_eq!(*address, key.p2wpkh_address(Network::Mainnet).unwrap());
+        }
+
+        // Testnet uses coin type 1'
+        let addresses = scan_addresses(&master, 1, 2, Network::Testnet).unwrap();
+        for (index, address) in addresses.iter().enumerate() {
+            let key = master.derive_path(&format!("m/84'/1'/1'/0/{}", index)).unwrap();
+            assert_eq!(*address, key.p2wpkh_address(Network::Testnet).unwrap());
+            assert!(address.starts_with("tb1q"));
+        }
+    }
+
+    #[test]
+    fn account_xpub_matches_bip44_bip49_and_bip84_vectors() {
+        let seed = crate::Bip39Generator::seed(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            None,
+        )
+        .unwrap();
+        let master = ExtendedPrivKey::new_master(&seed).unwrap();
+
+        assert_eq!(
+            master.account_xpub(44, 0, 0, Network::Mainnet).unwrap(),
+            "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj"
+        );
+        assert_eq!(
+            master.account_xpub(49, 1, 0, Network::Testnet).unwrap(),
+            "upub5EFU65HtV5TeiSHmZZm7FUffBGy8UKeqp7vw43jYbvZPpoVsgU93oac7Wk3u6moKegAEWtGNF8DehrnHtv21XXEMYRUocHqguyjknFHYfgY"
+        );
+        assert_eq!(
+            master.account_xpub(84, 0, 0, Network::Mainnet).unwrap(),
+            "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs"
+        );
+        assert!(master.account_xpub(45, 0, 0, Network::Mainnet).is_err());
+    }
+
+    #[test]
+    fn account_xpubs_match_account_xpub() {
+        let key = key();
+
+        let cases = [(44, Network::Mainnet), (49, Network::Testnet), (84, Network::Mainnet)];
+        for (purpose, network) in cases {
+            let xpubs = key.account_xpubs(purpose, 0, 0..4, network).unwrap();
+            assert_eq!(xpubs.keys().copied().collect::<Vec<u32>>(), [0, 1, 2, 3]);
+
+            for (&account, xpub) in &xpubs {
+                assert_eq!(*xpub, key.account_xpub(purpose, 0, account, network).unwrap());
+            }
+        }
+
+        assert!(key.account_xpubs(84, 0, 5..5, Network::Mainnet).unwrap().is_empty());
+        assert!(key.account_xpubs(45, 0, 0..1, Network::Mainnet).is_err());
+        assert!(key
+            .account_xpubs(84, 0, HARDENED_OFFSET - 1..HARDENED_OFFSET + 1, Network::Mainnet)
+            .is_err());
+    }
+
+    #[test]
+    fn p2tr_address_matches_bip86_vectors() {
+        let seed = crate::Bip39Generator::seed(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            None,
+        )
+        .unwrap();
+        let master = ExtendedPrivKey::new_master(&seed).unwrap();
+
+        let key = master.derive_path("m/86'/0'/0'/0/0").unwrap();
+        assert_eq!(
+            hex::encode(key.x_only_public_key()),
+            "cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115"
+        );
+        assert_eq!(
+            key.p2tr_address(Network::Mainnet).unwrap(),
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+        );
+
+        let key = master.derive_path("m/86'/0'/0'/0/1").unwrap();
+        assert_eq!(
+            key.p2tr_address(Network::Mainnet).unwrap(),
+            "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh"
+        );
+
+        let key = master.derive_path("m/86'/0'/0'/1/0").unwrap();
+        assert_eq!(
+            key.p2tr_address(Network::Mainnet).unwrap(),
+            "bc1p3qkhfews2uk44qtvauqyr2ttdsw7svhkl9nkm9s9c3x4ax5h60wqwruhk7"
+        );
+    }
+
+    #[test]
+    fn p2sh_p2wpkh_address_matches_bip49_vector() {
+        let seed = crate::Bip39Generator::seed(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            None,
+        )
+        .unwrap();
+        let master = ExtendedPrivKey::new_master(&seed).unwrap();
+
+        let key = master.derive_path("m/49'/1'/0'/0/0").unwrap();
+        assert_eq!(
+            key.p2sh_p2wpkh_address(Network::Testnet).unwrap(),
+            "2Mww8dCYPUpKHofjgcXcBCEGmniw9CoaiD2"
+        );
+    }
+
+    #[test]
+    fn p2wpkh_address_matches_bip84_vectors() {
+        let master = abandon_master();
+
+        let key = master.derive_path("m/84'/0'/0'/0/0").unwrap();
+        assert_eq!(
+            key.p2wpkh_address(Network::Mainnet).unwrap(),
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+        );
+
+        // The same key hash under the testnet prefix, which is what
+        // p2wpkh_address gives for a testnet network
+        let testnet = key.p2wpkh_address_with_hrp("tb").unwrap();
+        assert_eq!(testnet, key.p2wpkh_address(Network::Testnet).unwrap());
+        assert_eq!(testnet, "tb1qcr8te4kr609gcawutmrza0j4xv80jy8zmfp6l0");
+
+        // And the first testnet receive address from BIP84 itself
+        let key = master.derive_path("m/84'/1'/0'/0/0").unwrap();
+        assert_eq!(
+            key.p2wpkh_address_with_hrp("tb").unwrap(),
+            "tb1q6rz28mcfaxtmd6v789l9rrlrusdprr9pqcpvkl"
+        );
+
+        assert_eq!(
+            key.p2wpkh_address_with_hrp("not a prefix"),
+            Err(Bip39Error::InvalidHrp("not a prefix".to_owned()))
+        );
+    }
+
+    #[test]
+    fn wif_matches_known_encodings() {
+        let key = key();
+
+        assert_eq!(
+            key.to_wif(Network::Mainnet, KeyFormat::Uncompressed),
+            "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ"
+        );
+        assert_eq!(
+            key.to_wif(Network::Mainnet, KeyFormat::Compressed),
+            "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617"
+        );
+    }
+
+    #[test]
+    fn xprv_round_trips() {
+        // The master key and m/0'/1/2' of BIP32 test vector 1
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let master = ExtendedPrivKey::new_master(&seed).unwrap();
+        let vectors = [
+            (
+                master.clone(),
+                "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+            ),
+            (
+                master.derive_path("m/0'/1/2'").unwrap(),
+                "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+            ),
+        ];
+
+        for (key, xprv) in vectors {
+            let parsed = ExtendedPrivKey::from_xprv(xprv).unwrap();
+            assert_eq!(parsed, key);
+            assert_eq!(parsed.to_xprv(Network::Mainnet), xprv);
+
+            let tprv = key.to_xprv(Network::Testnet);
+            assert_eq!(ExtendedPrivKey::from_xprv(&tprv).unwrap(), key);
+
+            // We can keep deriving from the imported key
+            assert_eq!(
+                parsed.derive_child(7).unwrap(),
+                key.derive_child(7).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn from_xprv_rejects_other_keys() {
+        let key = key();
+
+        let xpub = key.to_xpub(Network::Mainnet);
+        assert!(matches!(
+            ExtendedPrivKey::from_xprv(&xpub),
+            Err(Bip39Error::InvalidExtendedKey(_))
+        ));
+
+        // A private key behind public key version bytes
+        let mislabeled = key.to_xprv_with_version(MAINNET_XPUB_VERSION);
+        assert!(matches!(
+            ExtendedPrivKey::from_xprv(&mislabeled),
+            Err(Bip39Error::InvalidExtendedKey(_))
+        ));
+
+        // A typo breaks the Base58Check checksum
+        let mut xprv = key.to_xprv(Network::Mainnet);
+        xprv.replace_range(20..21, if &xprv[20..21] == "a" { "b" } else { "a" });
+        assert!(matches!(
+            ExtendedPrivKey::from_xprv(&xprv),
+            Err(Bip39Error::InvalidExtendedKey(_))
+        ));
+
+        assert!(ExtendedPrivKey::from_xprv("").is_err());
+    }
+
+    #[test]
+    fn default_paths_follow_the_bips() {
+        assert_eq!(AddressType::Legacy.default_path(0, false, 0), "m/44'/0'/0'/0/0");
+        assert_eq!(AddressType::NestedSegwit.default_path(1, true, 7), "m/49'/0'/1'/1/7");
+        assert_eq!(AddressType::NativeSegwit.default_path(0, false, 5), "m/84'/0'/0'/0/5");
+        assert_eq!(AddressType::Taproot.default_path(2, true, 0), "m/86'/0'/2'/1/0");
+
+        // The path must be one `derive_path()` understands
+        let path = AddressType::NativeSegwit.default_path(0, false, 0);
+        assert_eq!(key().derive_path(&path).unwrap().depth(), 5);
+    }
+
+    #[test]
+    fn public_key_formats() {
+        let key = key();
+
+        let compressed = key.public_key_with_format(KeyFormat::Compressed);
+        assert_eq!(compressed, key.public_key());
+
+        let uncompressed = key.public_key_with_format(KeyFormat::Uncompressed);
+        assert_eq!(uncompressed.len(), 65);
+        assert_eq!(uncompressed[0], 0x04);
+        assert_eq!(uncompressed[1..33], compressed[1..]);
+        // The prefix of the compressed key is the parity of y
+        assert_eq!(compressed[0], 0x02 | (uncompressed[64] & 1));
+
+        assert_eq!(
+            key.p2pkh_address(Network::Mainnet, KeyFormat::Uncompressed).unwrap(),
+            "1GAehh7TsJAHuUAeKZcXf5CnwuGuGgyX2S"
+        );
+        assert_eq!(
+            key.p2pkh_address(Network::Mainnet, KeyFormat::Compressed).unwrap(),
+            "1LoVGDgRs9hTfTNJNuXKSpywcbdvwRXpmK"
+        );
+    }
+
+    #[test]
+    fn wif_round_trips_to_private_key() {
+        let key = key();
+
+        for network in [Network::Mainnet, Network::Testnet, Network::Regtest] {
+            for format in [KeyFormat::Uncompressed, KeyFormat::Compressed] {
+                let wif = key.to_wif(network, format);
+                let data = bs58::decode(wif).with_check(None).into_vec().unwrap();
+
+                assert_eq!(data[0], network.wif_prefix());
+                assert_eq!(&data[1..33], key.private_key());
+
+                if format.is_compressed() {
+                    assert_eq!(data.len(), 34);
+                    assert_eq!(data[33], 0x01);
+                } else {
+                    assert_eq!(data.len(), 33);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn derive_path_rejects_malformed_paths() {
+        let master = abandon_master();
+
+        for path in ["", "m/", "m/x", "m/+1", "m/2147483648", "m/2147483648'", "44'/0'"] {
+            assert_eq!(
+                master.derive_path(path),
+                Err(Bip39Error::InvalidDerivationPath(path.to_owned())),
+                "{:?} should be rejected",
+                path
+            );
+        }
+
+        // The largest index and both hardened markers are fine
+        assert!(master.derive_path("m/2147483647'").is_ok());
+        assert_eq!(master.derive_path("m/0h"), master.derive_path("m/0'"));
+        assert_eq!(master.derive_path("m").unwrap(), master);
+    }
+}
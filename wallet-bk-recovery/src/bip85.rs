@@ -0,0 +1,209 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use zeroize::Zeroizing;
+
+use crate::{
+    bip32::{ExtendedPrivKey, HARDENED_OFFSET},
+    Bip39Error, Bip39Generator, Language, Mnemonic, BITS_PER_WORD,
+};
+
+// The key BIP85 uses for the HMAC that turns a derived
+// private key into entropy
+pub const ENTROPY_HMAC_KEY: &[u8] = b"bip-entropy-from-k";
+// The first component of every BIP85 path, it spells
+// `SEED` when read as the ASCII codes of its digit pairs
+pub const BIP85_PURPOSE: u32 = 83696968;
+// The application number BIP85 assigns to BIP39 mnemonics
+pub const BIP39_APPLICATION: u32 = 39;
+
+// BIP85 numbers the BIP39 wordlists in the order they were
+// added to the spec. Portuguese was added later and has no
+// number, so we can't derive Portuguese child mnemonics.
+fn language_code(language: Language) -> Option<u32> {
+    match language {
+        Language::English => Some(0),
+        Language::Japanese => Some(1),
+        Language::Korean => Some(2),
+        Language::Spanish => Some(3),
+        Language::ChineseSimplified => Some(4),
+        Language::ChineseTraditional => Some(5),
+        Language::French => Some(6),
+        Language::Italian => Some(7),
+        Language::Czech => Some(8),
+        Language::Portuguese => None,
+    }
+}
+
+// This function derives a child mnemonic from our root key
+// as described in BIP85. We derive the key at
+// `m/83696968'/39'/{language}'/{words}'/{index}'`, then
+// HMAC-SHA512 its private key with the key "bip-entropy-from-k"
+// and keep as many bytes of the output as the mnemonic needs,
+// 16 bytes for 12 words, 24 for 18 and 32 for 24 words.
+// The same root key, language, word count and index always
+// give back the same mnemonic, so one backup covers them all.
+pub fn derive_mnemonic(
+    root_xprv: &ExtendedPrivKey,
+    language: Language,
+    words: u32,
+    index: u32,
+) -> Result<Mnemonic, Bip39Error> {
+    let language_code =
+        language_code(language).ok_or(Bip39Error::UnsupportedLanguage(language))?;
+
+    // BIP85 only defines child mnemonics of 12, 18 and 24 words
+    let length = match words {
+        12 => 16,
+        18 => 24,
+        24 => 32,
+        _ => return Err(Bip39Error::InvalidWordCount(words as usize, BITS_PER_WORD)),
+    };
+
+    let path = format!(
+        "m/{}'/{}'/{}'/{}'/{}'",
+        BIP85_PURPOSE, BIP39_APPLICATION, language_code, words, index
+    );
+    let child = root_xprv.derive_path(&path)?;
+
+    let mut mac = Hmac::<Sha512>::new_from_slice(ENTROPY_HMAC_KEY)
+        .expect("HMAC accepts keys of any length");
+    mac.update(child.private_key());
+    let output = Zeroizing::new(<[u8; 64]>::from(mac.finalize().into_bytes()));
+
+    // Keep only the first `length` bytes as our entropy
+    let entropy = &output[..length];
+
+    let mut generator = Bip39Generator::with_language(language);
+    let mnemonic = generator
+        .from_entropy(entropy)
+        .expect("embedded wordlists always hold 2048 words");
+
+    Ok(mnemonic)
+}
+
+// An iterator over the child mnemonics of a root key, calling
+// `derive_mnemonic()` for index 0, 1, 2 and so on with a fixed
+// language and word count, e.g.
+// `Bip85Iterator::new(&root, Language::English, 12).take(100)`.
+// Each child is only derived when we ask for it. BIP85 uses
+// hardened indexes so the iterator ends after index 2^31 - 1.
+#[derive(Debug, Clone)]
+pub struct Bip85Iterator<'a> {
+    root_xprv: &'a ExtendedPrivKey,
+    language: Language,
+    words: u32,
+    // The index of the next child, `None` once we ran out
+    next_index: Option<u32>,
+}
+
+impl<'a> Bip85Iterator<'a> {
+    // An iterator starting at the child at index 0
+    pub fn new(root_xprv: &'a ExtendedPrivKey, language: Language, words: u32) -> Self {
+        Self {
+            root_xprv,
+            language,
+            words,
+            next_index: Some(0),
+        }
+    }
+
+    // This makes the iterator start at `index` instead, which is
+    // cheaper than `skip()` since the children before it are
+    // never derived
+    pub fn starting_at(mut self, index: u32) -> Self {
+        self.next_index = Some(index).filter(|index| *index < HARDENED_OFFSET);
+        self
+    }
+}
+
+impl Iterator for Bip85Iterator<'_> {
+    type Item = Result<Mnemonic, Bip39Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.next_index?;
+        self.next_index = index.checked_add(1).filter(|next| *next < HARDENED_OFFSET);
+
+        Some(derive_mnemonic(
+            self.root_xprv,
+            self.language,
+            self.words,
+            index,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The root key of the BIP85 test vectors
+    const ROOT_XPRV: &str = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
+
+    fn root() -> ExtendedPrivKey {
+        ExtendedPrivKey::from_xprv(ROOT_XPRV).unwrap()
+    }
+
+    #[test]
+    fn derive_mnemonic_matches_bip85_vectors() {
+        let vectors = [
+            (12, "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose"),
+            (
+                18,
+                "near account window bike charge season chef number sketch tomorrow excuse \
+                 sniff circle vital hockey outdoor supply token",
+            ),
+            (
+                24,
+                "puppy ocean match cereal symbol another shed magic wrap hammer bulb intact \
+                 gadget divorce twin tonight reason outdoor destroy simple truth cigar social \
+                 volcano",
+            ),
+        ];
+
+        for (words, expected) in vectors {
+            let mnemonic = derive_mnemonic(&root(), Language::English, words, 0).unwrap();
+            assert_eq!(mnemonic.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn derive_mnemonic_rejects_unsupported_words_and_languages() {
+        assert_eq!(
+            derive_mnemonic(&root(), Language::English, 15, 0).unwrap_err(),
+            Bip39Error::InvalidWordCount(15, BITS_PER_WORD)
+        );
+        assert_eq!(
+            derive_mnemonic(&root(), Language::Portuguese, 12, 0).unwrap_err(),
+            Bip39Error::UnsupportedLanguage(Language::Portuguese)
+        );
+    }
+
+    #[test]
+    fn iterator_yields_the_children_in_order() {
+        let root = root();
+
+        let children = Bip85Iterator::new(&root, Language::English, 12)
+            .take(3)
+            .collect::<Result<Vec<Mnemonic>, Bip39Error>>()
+            .unwrap();
+        assert_eq!(children.len(), 3);
+        for (index, child) in children.iter().enumerate() {
+            let expected = derive_mnemonic(&root, Language::English, 12, index as u32).unwrap();
+            assert_eq!(child.to_string(), expected.to_string());
+        }
+
+        let mut from_five = Bip85Iterator::new(&root, Language::English, 12).starting_at(5);
+        let expected = derive_mnemonic(&root, Language::English, 12, 5).unwrap();
+        assert_eq!(from_five.next().unwrap().unwrap().to_string(), expected.to_string());
+
+        // The last hardened index ends the iterator
+        let mut last = Bip85Iterator::new(&root, Language::English, 12)
+            .starting_at(HARDENED_OFFSET - 1);
+        assert!(last.next().is_some());
+        assert!(last.next().is_none());
+        assert!(Bip85Iterator::new(&root, Language::English, 12)
+            .starting_at(HARDENED_OFFSET)
+            .next()
+            .is_none());
+    }
+}
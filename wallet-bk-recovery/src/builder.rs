@@ -0,0 +1,96 @@
+use std::path::{Path, PathBuf};
+
+use crate::{Bip39Generator, Language, Network};
+
+// A builder for `Bip39Generator` so the language, network,
+// iteration count and wordlist path can be set together.
+// Anything we don't set keeps its default, which is the
+// embedded English wordlist, mainnet and the 2048 PBKDF2
+// iterations of BIP39, e.g.
+// `Bip39Generator::builder().language(Language::French).build()`
+#[derive(Debug, Default, Clone)]
+pub struct Bip39GeneratorBuilder {
+    language: Language,
+    network: Network,
+    iterations: Option<u32>,
+    wordlist_path: Option<PathBuf>,
+    streaming: bool,
+    bits_per_word: Option<usize>,
+}
+
+impl Bip39GeneratorBuilder {
+    // A builder holding the defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The language of the wordlist, which also decides how the
+    // words of a mnemonic are joined together
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    // The network the keys of our seeds are meant for, which
+    // decides how `Bip39Generator::master_xprv()` and
+    // `Bip39Generator::account_xpub()` serialize them
+    pub fn network(mut self, network: Network) -> Self {
+        self.network = network;
+        self
+    }
+
+    // The number of PBKDF2 iterations used for every seed the
+    // generator derives. Anything other than `ITERATION_COUNT`
+    // gives seeds no other BIP39 wallet will reproduce, and 0 is
+    // rejected with `Bip39Error::InvalidIterationCount` as soon
+    // as a seed is derived.
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        self.iterations = Some(iterations);
+        self
+    }
+
+    // Read the words from this file instead of the embedded
+    // wordlist of our language. The language still decides
+    // the separator so it should match the file.
+    pub fn wordlist_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.wordlist_path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    // Only read the words we need while generating, see
+    // `Bip39Generator::streaming()`
+    pub fn streaming(mut self, enabled: bool) -> Self {
+        self.streaming = enabled;
+        self
+    }
+
+    // The number of bits each word stands for, for a custom scheme
+    // that packs its mnemonics like BIP39 but into a wordlist of
+    // `2^bits` words, e.g. 10 bits for a list of 1024 words. The
+    // checksum stays the BIP39 one and the last word is padded
+    // with zero bits when needed. Indexes are `u16`s so `bits`
+    // must be between 1 and 16, anything else panics.
+    pub fn bits_per_word(mut self, bits: usize) -> Self {
+        assert!(
+            (1..=16).contains(&bits),
+            "words of {} bits don't fit in a u16 index",
+            bits
+        );
+        self.bits_per_word = Some(bits);
+        self
+    }
+
+    // This creates the generator. Nothing is read from disk yet,
+    // a wordlist file is only loaded the first time it is needed.
+    pub fn build(self) -> Bip39Generator {
+        Bip39Generator {
+            path: self.wordlist_path,
+            language: self.language,
+            network: self.network,
+            iterations: self.iterations,
+            streaming: self.streaming,
+            bits_per_word: self.bits_per_word,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use pbkdf2::pbkdf2_hmac;
+use sha2::{Digest, Sha256, Sha512};
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+use crate::{
+    checksum_bits, total_bits, word_count_with, Bip39Error, BITS_PER_WORD, ENTROPY_SIZES,
+    ITERATION_COUNT, SALT_PREFIX, SEED_LENGTH,
+};
+
+// The parts of BIP39 that are pure computation live here: the
+// checksum, packing bits into word indexes and deriving the
+// seed. They only need `alloc` so they also build without the
+// `std` feature, e.g. on an embedded device that looks words
+// up in `Language::wordlist()` instead of reading a file.
+
+// This function computes the BIP39 checksum of `entropy`, its
+// checksum bits in the high bits of a byte. BIP39 only defines
+// a checksum for the entropy sizes of `ENTROPY_SIZES`, any other
+// length is rejected with `Bip39Error::InvalidEntropyLength`.
+pub fn checksum(entropy: &[u8]) -> Result<u8, Bip39Error> {
+    if !ENTROPY_SIZES.contains(&entropy.len()) {
+        return Err(Bip39Error::InvalidEntropyLength(entropy.len()));
+    }
+
+    // BIP39 spec requires a seed to be generated
+    // using a SHA256 Psuedo Random Function (PRF)
+    // so we instantiate a SHA256 hashing function.
+    let mut hasher = Sha256::new();
+
+    // We now pass our random bytes into our SHA256 PRF
+    hasher.update(entropy);
+
+    // We now get our finalized value. Using
+    // SHA256 always ensures that despite being
+    // able to use variable length of random bytes
+    // we always get back a 256 bit (32 byte) value.
+    let entropy_hash = hasher.finalize();
+
+    // Since we get a 32 byte value we multiply by
+    // `8` to get number of bits since 1 byte == 8 bits
+    let bits_of_entropy = entropy.len() * 8;
+    // We get our `n` bits for our checksum from the
+    // length of the random bits (entropy)
+    // where `n` is calculated as the
+    // `length of our random bits / 32`
+    let bits_of_checksum = bits_of_entropy / 32;
+    // We then use a bit mask to keep only the first
+    // `bits_of_checksum` bits of our 256 bit hash in
+    // variable `entropy_hash`. The checksum bits must stay
+    // in the high bits of the byte because the bits are
+    // read from the most significant bit first,
+    // so the low bits are just padding that gets dropped.
+    //
+    // `bits_of_checksum` ranges from 4 (128 bit entropy) to
+    // 8 (256 bit entropy). Shifting a `u8` by 8 would overflow,
+    // so we build the mask in a `u16` and keep its low byte,
+    // e.g. 0xFF00 >> 4 == 0x0FF0 -> 0xF0 and 0xFF00 >> 8 == 0x00FF -> 0xFF
+    let checksum_mask = (0xFF00u16 >> bits_of_checksum) as u8;
+
+    Ok(entropy_hash[0] & checksum_mask)
+}
+
+// This function splits a stream of bits, most significant bit
+// first, into the 11 bit indexes of the words of a mnemonic.
+// The stream has to be the entropy followed by its checksum,
+// which is always a multiple of 11 bits long. Any other length
+// would leave a partial word at the end so we panic instead of
+// quietly dropping it.
+pub fn bits_to_indices(bits: &[bool]) -> Vec<u16> {
+    bits_to_indices_with(bits, BITS_PER_WORD)
+}
+
+// Same as `bits_to_indices()` for a wordlist of
+// `2^bits_per_word` words instead of the 2048 of BIP39. An index
+// is a `u16` so words can hold between 1 and 16 bits.
+pub fn bits_to_indices_with(bits: &[bool], bits_per_word: usize) -> Vec<u16> {
+    assert!(
+        (1..=16).contains(&bits_per_word),
+        "words of {} bits don't fit in a u16 index",
+        bits_per_word
+    );
+    assert!(
+        bits.len().is_multiple_of(bits_per_word),
+        "{} bits can't be split into {} bit words",
+        bits.len(),
+        bits_per_word
+    );
+
+    bits.chunks_exact(bits_per_word)
+        .map(|chunk| {
+            // Each bit that is set adds `2^(bits_per_word - 1 - i)` to
+            // the index, the first bit being the most significant
+            chunk
+                .iter()
+                .fold(0u16, |value, &bit| (value << 1) | u16::from(bit))
+        })
+        .collect()
+}
+
+// This function is the reverse of `bits_to_indices()`, turning
+// each index back into its 11 bits, most significant bit first.
+// Only the low 11 bits of an index are used.
+pub fn indices_to_bits(indices: &[u16]) -> Vec<bool> {
+    indices_to_bits_with(indices, BITS_PER_WORD)
+}
+
+// Same as `indices_to_bits()` with `bits_per_word` bits per index
+pub fn indices_to_bits_with(indices: &[u16], bits_per_word: usize) -> Vec<bool> {
+    let mut bits = Vec::with_capacity(indices.len() * bits_per_word);
+
+    for &index in indices {
+        for i in (0..bits_per_word).rev() {
+            bits.push((index >> i) & 1u16 == 1);
+        }
+    }
+
+    bits
+}
+
+// This function turns entropy into the wordlist indexes of its
+// mnemonic, appending the checksum and splitting the bits into
+// 11 bit indexes
+pub fn entropy_to_indices(entropy: &[u8]) -> Result<Vec<u16>, Bip39Error> {
+    entropy_to_indices_with(entropy, BITS_PER_WORD)
+}
+
+// Same as `entropy_to_indices()` with `bits_per_word` bits per
+// index. When the entropy and checksum bits don't split evenly
+// into words, which never happens with the 11 bits of BIP39, the
+// last word is padded with zero bits.
+pub fn entropy_to_indices_with(entropy: &[u8], bits_per_word: usize) -> Result<Vec<u16>, Bip39Error> {
+    if !ENTROPY_SIZES.contains(&entropy.len()) {
+        return Err(Bip39Error::InvalidEntropyLength(entropy.len()));
+    }
+
+    let checksum = checksum(entropy)?;
+
+    let mut bits = Zeroizing::new(Vec::<bool>::with_capacity(entropy.len() * 8 + 8));
+    for &byte in entropy.iter().chain(core::iter::once(&checksum)) {
+        for i in (0..8).rev() {
+            bits.push((byte >> i) & 1u8 == 1);
+        }
+    }
+    bits.truncate(total_bits(entropy.len()));
+    bits.resize(word_count_with(entropy.len(), bits_per_word) * bits_per_word, false);
+
+    Ok(bits_to_indices_with(&bits, bits_per_word))
+}
+
+// This function is the reverse of `entropy_to_indices()`. It
+// packs the indexes back into entropy and checks the checksum,
+// so it tells us whether a mnemonic is valid once its words
+// have been looked up.
+pub fn indices_to_entropy(indices: &[u16]) -> Result<Vec<u8>, Bip39Error> {
+    indices_to_entropy_with(indices, BITS_PER_WORD)
+}
+
+// Same as `indices_to_entropy()` with `bits_per_word` bits per
+// index, the reverse of `entropy_to_indices_with()`. Padding
+// bits must be zero like the checksum bits must match.
+pub fn indices_to_entropy_with(indices: &[u16], bits_per_word: usize) -> Result<Vec<u8>, Bip39Error> {
+    // BIP39 only defines mnemonics of 12, 15, 18, 21 and 24 words,
+    // the number of words tells us how much entropy they hold
+    let entropy_len = ENTROPY_SIZES
+        .iter()
+        .copied()
+        .find(|&n| word_count_with(n, bits_per_word) == indices.len())
+        .ok_or(Bip39Error::InvalidWordCount(indices.len(), bits_per_word))?;
+
+    let bits = Zeroizing::new(indices_to_bits_with(indices, bits_per_word));
+
+    // Every 33 bits of the mnemonic carry 32 bits of
+    // entropy and 1 bit of checksum, anything after
+    // them is padding
+    let bits_of_checksum = checksum_bits(entropy_len);
+    let bits_of_entropy = entropy_len * 8;
+
+    if bits[total_bits(entropy_len)..].iter().any(|&bit| bit) {
+        return Err(Bip39Error::InvalidChecksum);
+    }
+
+    // Pack the entropy bits back into bytes
+    let entropy = bits[..bits_of_entropy]
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0u8, |acc, &bit| (acc << 1) | bit as u8))
+        .collect::<Vec<u8>>();
+
+    // The checksum is the first `bits_of_checksum` bits of
+    // the hash so we compare them one by one against the
+    // trailing bits of the mnemonic
+    let checksum = checksum(&entropy)?;
+    let valid = (0..bits_of_checksum)
+        .all(|i| bits[bits_of_entropy + i] == ((checksum >> (7 - i)) & 1u8 == 1));
+
+    if !valid {
+        return Err(Bip39Error::InvalidChecksum);
+    }
+
+    Ok(entropy)
+}
+
+// The work that goes into stretching a mnemonic into its seed,
+// for a UI that wants to tell users what is happening while
+// the seed is derived, e.g. "2048 rounds of HMAC-SHA512".
+// Each PBKDF2 iteration is one round of the HMAC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeedParams {
+    pub iterations: u32,
+    pub algorithm: &'static str,
+}
+
+impl SeedParams {
+    // What BIP39 mandates and what `derive_seed()` runs unless
+    // it is given another iteration count
+    pub const BIP39: SeedParams = SeedParams {
+        iterations: ITERATION_COUNT,
+        algorithm: "HMAC-SHA512",
+    };
+}
+
+impl Default for SeedParams {
+    fn default() -> Self {
+        SeedParams::BIP39
+    }
+}
+
+impl fmt::Display for SeedParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} rounds of {}", self.iterations, self.algorithm)
+    }
+}
+
+// This function derives the 512 bit seed of a mnemonic with
+// PBKDF2-HMAC-SHA512, using `iterations` rounds where BIP39
+// requires `ITERATION_COUNT`
+pub fn derive_seed(
+    mnemonic: &str,
+    passphrase: Option<&str>,
+    iterations: u32,
+) -> Result<[u8; SEED_LENGTH], Bip39Error> {
+    derive_seed_with_prefix(mnemonic, passphrase, SALT_PREFIX, iterations)
+}
+
+// Same as `derive_seed()` but the salt starts with `prefix`
+// instead of the "mnemonic" BIP39 requires. This is only meant
+// for experimenting with derivation variants: any other prefix
+// gives a seed that no BIP39 wallet will reproduce from the
+// same mnemonic, so funds sent to its keys can only be
+// recovered by software that knows the prefix too.
+pub fn derive_seed_with_prefix(
+    mnemonic: &str,
+    passphrase: Option<&str>,
+    prefix: &str,
+    iterations: u32,
+) -> Result<[u8; SEED_LENGTH], Bip39Error> {
+    // PBKDF2 needs to run at least once
+    if iterations == 0 {
+        return Err(Bip39Error::InvalidIterationCount);
+    }
+
+    // We check if there is a passphrase provided.
+    // if there is one we prefix our salt with the passphrase.
+    // BIP39 defines no passphrase as the empty passphrase, so
+    // `Some("")` takes the same path as `None` and both give
+    // a salt of just the prefix.
+    // Just like the mnemonic, BIP39 requires the passphrase
+    // to be NFKD normalized so that accented or composed
+    // characters give the same seed as hardware wallets.
+    //
+    // The salt holds the passphrase so it is wiped once we are
+    // done with it. We reserve exactly the room the normalized
+    // passphrase needs upfront, a single character can grow into
+    // many under NFKD, so that growing the string never moves it
+    // and leaves a copy of the passphrase behind.
+    let salt = if let Some(passphrase_required) = passphrase.filter(|p| !p.is_empty()) {
+        let mut salt = Zeroizing::new(String::with_capacity(
+            prefix.len() + nfkd_len(passphrase_required),
+        ));
+        salt.push_str(prefix);
+        salt.extend(passphrase_required.nfkd());
+        salt
+    } else {
+        Zeroizing::new(String::from(prefix))
+    };
+
+    // BIP39 requires the mnemonic sentence to be normalized
+    // to Unicode NFKD before it is fed to PBKDF2. This doesn't
+    // change pure ASCII (English) mnemonics but matters for
+    // wordlists containing composed characters.
+    let mut normalized_mnemonic = Zeroizing::new(String::with_capacity(nfkd_len(mnemonic)));
+    normalized_mnemonic.extend(mnemonic.nfkd());
+
+    // We want to generate a 512bit seed
+    // so we create a buffer to hold this.
+    let mut seed = [0u8; SEED_LENGTH]; // 512 bits == 64 bytes
+
+    // We generate a key and push all the bytes to the `seed` buffer
+    pbkdf2_hmac::<Sha512>(
+        normalized_mnemonic.as_bytes(),
+        salt.as_bytes(),
+        iterations,
+        &mut seed,
+    );
+
+    // Wipe the passphrase and mnemonic as soon as PBKDF2 is done
+    // with them instead of waiting for the end of the function
+    drop(salt);
+    drop(normalized_mnemonic);
+
+    Ok(seed)
+}
+
+// The number of bytes `text` takes up once NFKD normalized
+fn nfkd_len(text: &str) -> usize {
+    text.nfkd().map(char::len_utf8).sum()
+}
+
+// Same as `derive_seed()` with the 2048 iterations of BIP39
+pub fn seed(mnemonic: &str, passphrase: Option<&str>) -> Result<[u8; SEED_LENGTH], Bip39Error> {
+    derive_seed(mnemonic, passphrase, ITERATION_COUNT)
+}
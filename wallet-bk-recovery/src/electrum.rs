@@ -0,0 +1,269 @@
+use hmac::{Hmac, Mac};
+use rand_core::RngCore;
+use sha2::Sha512;
+use unicode_normalization::{char::canonical_combining_class, UnicodeNormalization};
+use zeroize::Zeroizing;
+
+use crate::{
+    entropy_from_words, system_rng, Bip39Generator, Language, Mnemonic, BITS_PER_WORD,
+    WORDLIST_LENGTH,
+};
+
+// Electrum seeds are NOT BIP39 mnemonics. They use the same
+// English wordlist but have no checksum, instead the words
+// are picked so that HMAC-SHA512 of the normalized sentence
+// starts with a version prefix that also tells Electrum which
+// kind of wallet to restore. Nothing in this module is used by
+// the BIP39 code of the crate and a seed from here can't be
+// recovered with `Bip39Generator::recover()`.
+
+// The key Electrum uses for the HMAC of the seed version
+pub const SEED_VERSION_HMAC_KEY: &[u8] = b"Seed version";
+// The number of words of the seeds we generate, like Electrum
+// we use 132 bits which is 12 words of 11 bits
+pub const ELECTRUM_WORD_COUNT: usize = 12;
+
+// The kinds of wallet an Electrum seed can be for, each has
+// its own prefix of the hex encoded HMAC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElectrumSeedType {
+    // Legacy P2PKH wallets, prefix `01`
+    Standard,
+    // Native segwit wallets, prefix `100`
+    Segwit,
+    // Two factor wallets, prefix `101`
+    TwoFactor,
+    // Two factor segwit wallets, prefix `102`
+    TwoFactorSegwit,
+}
+
+impl ElectrumSeedType {
+    // Every seed type, in the order we check their prefixes
+    pub const ALL: [ElectrumSeedType; 4] = [
+        ElectrumSeedType::Standard,
+        ElectrumSeedType::Segwit,
+        ElectrumSeedType::TwoFactor,
+        ElectrumSeedType::TwoFactorSegwit,
+    ];
+
+    // The hex prefix the HMAC of a seed of this type starts with
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            ElectrumSeedType::Standard => "01",
+            ElectrumSeedType::Segwit => "100",
+            ElectrumSeedType::TwoFactor => "101",
+            ElectrumSeedType::TwoFactorSegwit => "102",
+        }
+    }
+}
+
+// Electrum treats these ranges as CJK when removing the spaces
+// between characters, they cover the Chinese, Japanese and
+// Korean scripts and their punctuation
+const CJK_INTERVALS: [(u32, u32); 19] = [
+    (0x1100, 0x11FF),
+    (0x2E80, 0x2EFF),
+    (0x2F00, 0x2FDF),
+    (0x2FF0, 0x2FFF),
+    (0x3000, 0x303F),
+    (0x3040, 0x309F),
+    (0x30A0, 0x30FF),
+    (0x3100, 0x312F),
+    (0x3130, 0x318F),
+    (0x3190, 0x319F),
+    (0x31A0, 0x31BF),
+    (0x31F0, 0x31FF),
+    (0x3300, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xAC00, 0xD7AF),
+    (0xF900, 0xFAFF),
+    (0xFF00, 0xFFEF),
+    (0x20000, 0x2A6DF),
+];
+
+fn is_cjk(c: char) -> bool {
+    CJK_INTERVALS
+        .iter()
+        .any(|&(start, end)| (start..=end).contains(&(c as u32)))
+}
+
+// Electrum normalizes seeds differently from BIP39. On top of
+// NFKD it lowercases the text, drops combining marks such as
+// accents, collapses whitespace into single spaces and removes
+// the spaces between two CJK characters.
+pub fn normalize(mnemonic: &str) -> Zeroizing<String> {
+    let lowered = Zeroizing::new(
+        mnemonic
+            .nfkd()
+            .collect::<String>()
+            .to_lowercase()
+            .chars()
+            .filter(|c| canonical_combining_class(*c) == 0)
+            .collect::<String>(),
+    );
+
+    let chars = Zeroizing::new(
+        lowered
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .chars()
+            .collect::<Vec<char>>(),
+    );
+
+    let mut normalized = Zeroizing::new(String::with_capacity(chars.len()));
+    for (index, c) in chars.iter().enumerate() {
+        let between_cjk = *c == ' '
+            && index > 0
+            && is_cjk(chars[index - 1])
+            && chars.get(index + 1).is_some_and(|next| is_cjk(*next));
+
+        if !between_cjk {
+            normalized.push(*c);
+        }
+    }
+
+    normalized
+}
+
+// This function tells us which kind of Electrum seed the
+// mnemonic is, or `None` when it isn't an Electrum seed
+pub fn is_electrum_seed(mnemonic: &str) -> Option<ElectrumSeedType> {
+    let normalized = normalize(mnemonic);
+
+    let mut mac = Hmac::<Sha512>::new_from_slice(SEED_VERSION_HMAC_KEY)
+        .expect("HMAC accepts keys of any length");
+    mac.update(normalized.as_bytes());
+    let version = hex::encode(mac.finalize().into_bytes());
+
+    ElectrumSeedType::ALL
+        .into_iter()
+        .find(|kind| version.starts_with(kind.prefix()))
+}
+
+// This function generates a new English Electrum seed of the
+// kind we ask for. Like Electrum we read our entropy as a
+// number written in base 2048, one word per digit starting
+// with the least significant, and keep adding 1 to it until
+// the seed has the right version prefix. That takes 256 tries
+// on average for a standard seed and 4096 for the others.
+// We also skip seeds that happen to be valid BIP39 mnemonics
+// so a wallet can't mistake one for the other. Electrum's
+// `make_seed` additionally skips seeds that read as valid old
+// (pre 2.0) seeds, whose words all come from its old 1626 word
+// list. We don't ship that list so we don't check it. Only
+// words that appear in both lists could form one, which makes
+// such a seed very unlikely, but a seed of ours isn't
+// guaranteed to be one Electrum itself could have made.
+pub fn generate(kind: ElectrumSeedType) -> Mnemonic {
+    let wordlist = Language::English.wordlist().lines().collect::<Vec<&str>>();
+    let generator = Bip39Generator::with_language(Language::English);
+    let word_index = generator
+        .load_word_index()
+        .expect("embedded wordlists always hold 2048 words");
+
+    let mut rng = system_rng();
+    let mut digits = Zeroizing::new([0u16; ELECTRUM_WORD_COUNT]);
+
+    loop {
+        // Electrum makes sure the most significant digit is not
+        // zero so the seed never comes out shorter than 12 words
+        for digit in digits.iter_mut() {
+            *digit = (rng.next_u32() % WORDLIST_LENGTH as u32) as u16;
+        }
+        if digits[ELECTRUM_WORD_COUNT - 1] == 0 {
+            continue;
+        }
+
+        // Try consecutive numbers until one has our prefix. If
+        // we ever run past the largest 12 word number we start
+        // over from new entropy instead of growing a 13th word.
+        while increment(&mut digits) {
+            let sentence = Zeroizing::new(
+                digits
+                    .iter()
+                    .map(|&digit| wordlist[digit as usize])
+                    .collect::<Vec<&str>>()
+                    .join(" "),
+            );
+
+            if is_electrum_seed(&sentence) == Some(kind)
+                && entropy_from_words(word_index, &sentence).is_err()
+            {
+                let words = sentence.split(' ').map(str::to_owned).collect();
+
+                return Mnemonic::new(words, digits.to_vec(), Language::English, BITS_PER_WORD);
+            }
+        }
+    }
+}
+
+// This function adds 1 to the base 2048 number in `digits`,
+// returning `false` when it overflows the 12 digits
+fn increment(digits: &mut [u16; ELECTRUM_WORD_COUNT]) -> bool {
+    for digit in digits.iter_mut() {
+        if (*digit as usize) < WORDLIST_LENGTH - 1 {
+            *digit += 1;
+            return true;
+        }
+        *digit = 0;
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_electrum_seed_knows_every_seed_type() {
+        // Seeds from the Electrum test suite
+        let seeds = [
+            (
+                "cycle rocket west magnet parrot shuffle foot correct salt library feed song",
+                ElectrumSeedType::Standard,
+            ),
+            (
+                "bitter grass shiver impose acquire brush forget axis eager alone wine silver",
+                ElectrumSeedType::Segwit,
+            ),
+            (
+                "kiss live scene rude gate step hip quarter bunker oxygen motor glove",
+                ElectrumSeedType::TwoFactor,
+            ),
+            (
+                "universe topic remind silver february ranch shine worth innocent cattle enhance wise",
+                ElectrumSeedType::TwoFactorSegwit,
+            ),
+        ];
+
+        for (seed, kind) in seeds {
+            assert_eq!(is_electrum_seed(seed), Some(kind));
+        }
+    }
+
+    #[test]
+    fn is_electrum_seed_rejects_bip39_mnemonics() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                        abandon abandon about";
+        assert_eq!(is_electrum_seed(mnemonic), None);
+    }
+
+    #[test]
+    fn generate_gives_seeds_of_the_requested_type() {
+        for kind in ElectrumSeedType::ALL {
+            let seed = generate(kind);
+            assert_eq!(seed.word_count(), ELECTRUM_WORD_COUNT);
+            assert_eq!(is_electrum_seed(&seed.to_string()), Some(kind));
+        }
+    }
+
+    #[test]
+    fn normalize_strips_accents_and_spaces_between_cjk() {
+        assert_eq!(normalize("  Très   Élevé ").as_str(), "tres eleve");
+        // The dakuten of が and ぎ are combining marks in NFKD
+        assert_eq!(normalize("が ぎ\u{3000}く abc").as_str(), "かきく abc");
+    }
+}
@@ -0,0 +1,158 @@
+use alloc::{string::String, vec::Vec};
+use core::{fmt, slice};
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::Language;
+
+// The errors our key derivation and mnemonic types can run into.
+// The generator itself still reports `io::Error`s since it reads
+// wordlists from disk, so we also convert into an `io::Error`
+// to let both be handled with `?`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bip39Error {
+    // BIP32 only accepts seeds between 128 and 512 bits
+    // and our BIP39 seeds are always exactly 512 bits
+    InvalidSeedLength(usize),
+    // The derived key is zero or not below the secp256k1 curve order
+    InvalidPrivateKey,
+    // A derivation path that doesn't look like `m/44'/0'/0'/0/0`
+    InvalidDerivationPath(String),
+    // Extended keys store their depth in a single byte
+    MaxDepthExceeded,
+    // A string that should be a serialized extended key isn't
+    InvalidExtendedKey(String),
+    // The human readable part of a bech32 address is not valid
+    InvalidHrp(String),
+    // The address could not be encoded
+    AddressEncoding(String),
+    // A word of the mnemonic is not in the wordlist
+    UnknownWord(String),
+    // A number of words no mnemonic can have, along with the bits
+    // per word of the wordlist, which decide the counts we accept.
+    // BIP39 mnemonics of 11 bit words have 12, 15, 18, 21 or 24
+    InvalidWordCount(usize, usize),
+    // The checksum bits don't match the entropy
+    InvalidChecksum,
+    // A string that should be hex isn't
+    InvalidHex(String),
+    // BIP39 entropy is 16, 20, 24, 28 or 32 bytes
+    InvalidEntropyLength(usize),
+    // PBKDF2 needs to run at least once
+    InvalidIterationCount,
+    // An empty passphrase gives the same seed as none at all
+    EmptyPassphrase,
+    // There is no BIP39 wordlist for a language of this name
+    UnknownLanguage(String),
+    // There is no Bitcoin network of this name
+    UnknownNetwork(String),
+    // The language has no code in the spec we are following
+    UnsupportedLanguage(Language),
+    // Shamir shares that are malformed or don't belong together
+    InvalidShare(String),
+    // Fewer Shamir shares than the threshold, needed and given
+    NotEnoughShares(usize, usize),
+    // A mnemonic with more than one problem, every unknown word,
+    // a wrong word count or both, see `problems()`
+    InvalidMnemonic(Vec<Bip39Error>),
+}
+
+impl Bip39Error {
+    // This returns every problem behind the error so a recovery
+    // UI can show them all at once. That is the list inside
+    // `InvalidMnemonic` and just the error itself otherwise.
+    pub fn problems(&self) -> &[Bip39Error] {
+        match self {
+            Bip39Error::InvalidMnemonic(problems) => problems,
+            error => slice::from_ref(error),
+        }
+    }
+
+    // This writes what is wrong with a mnemonic without the
+    // "invalid mnemonic: " prefix, so several problems can
+    // share one prefix
+    fn fmt_mnemonic_problem(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bip39Error::UnknownWord(word) => write!(f, "`{}` is not in the wordlist", word),
+            Bip39Error::InvalidWordCount(count, bits_per_word) => write!(
+                f,
+                "{} words, expected {}",
+                count,
+                crate::describe_word_counts(*bits_per_word)
+            ),
+            Bip39Error::InvalidChecksum => write!(f, "checksum mismatch"),
+            error => write!(f, "{}", error),
+        }
+    }
+}
+
+impl fmt::Display for Bip39Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bip39Error::InvalidSeedLength(length) => {
+                write!(f, "invalid seed length: {} bytes", length)
+            }
+            Bip39Error::InvalidPrivateKey => write!(f, "invalid private key"),
+            Bip39Error::InvalidDerivationPath(path) => {
+                write!(f, "invalid derivation path: `{}`", path)
+            }
+            Bip39Error::MaxDepthExceeded => {
+                write!(f, "cannot derive more than 255 levels deep")
+            }
+            Bip39Error::InvalidExtendedKey(reason) => {
+                write!(f, "invalid extended key: {}", reason)
+            }
+            Bip39Error::InvalidHrp(hrp) => write!(f, "invalid bech32 hrp: `{}`", hrp),
+            Bip39Error::AddressEncoding(reason) => {
+                write!(f, "failed to encode address: {}", reason)
+            }
+            Bip39Error::UnknownWord(_)
+            | Bip39Error::InvalidWordCount(..)
+            | Bip39Error::InvalidChecksum => {
+                f.write_str("invalid mnemonic: ")?;
+                self.fmt_mnemonic_problem(f)
+            }
+            Bip39Error::InvalidHex(reason) => write!(f, "invalid hex: {}", reason),
+            Bip39Error::InvalidEntropyLength(length) => write!(
+                f,
+                "{} bytes is not a valid BIP39 entropy size, expected one of {:?}",
+                length,
+                crate::ENTROPY_SIZES
+            ),
+            Bip39Error::InvalidIterationCount => write!(f, "PBKDF2 needs at least one iteration"),
+            Bip39Error::EmptyPassphrase => write!(
+                f,
+                "empty passphrase, generate without a passphrase instead"
+            ),
+            Bip39Error::UnknownLanguage(name) => write!(f, "unknown language: `{}`", name),
+            Bip39Error::UnknownNetwork(name) => write!(f, "unknown network: `{}`", name),
+            Bip39Error::UnsupportedLanguage(language) => {
+                write!(f, "unsupported language: {:?}", language)
+            }
+            Bip39Error::InvalidShare(reason) => write!(f, "invalid share: {}", reason),
+            Bip39Error::NotEnoughShares(needed, given) => {
+                write!(f, "not enough shares: need {}, got {}", needed, given)
+            }
+            Bip39Error::InvalidMnemonic(problems) => {
+                f.write_str("invalid mnemonic: ")?;
+                for (index, problem) in problems.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str("; ")?;
+                    }
+                    problem.fmt_mnemonic_problem(f)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Bip39Error {}
+
+#[cfg(feature = "std")]
+impl From<Bip39Error> for io::Error {
+    fn from(error: Bip39Error) -> Self {
+        io::Error::new(io::ErrorKind::InvalidInput, error)
+    }
+}
@@ -0,0 +1,104 @@
+use std::slice;
+
+use zeroize::Zeroizing;
+
+use crate::{Bip39Error, Bip39Generator, Language};
+
+// These are the functions we export to C when building with the
+// `ffi` feature, and through C to Swift, Kotlin or anything else
+// that can call a C library. Const generics don't exist on the
+// other side so the number of words is a plain argument, and
+// just like the `wasm` bindings we always use the embedded
+// English wordlist. Nothing is allocated for the caller, which
+// passes in a buffer and gets an error code back, see
+// `include/wallet_bk_recovery.h`.
+
+// Everything went fine
+pub const FFI_OK: i32 = 0;
+// The word count is not one the wordlist can give, 12, 15, 18,
+// 21 or 24 for the BIP39 English list, see `describe_word_counts()`
+pub const FFI_INVALID_WORD_COUNT: i32 = -1;
+// `out` or `out_len` is a null pointer
+pub const FFI_NULL_POINTER: i32 = -2;
+// The buffer is too small, `*out_len` now holds the size needed
+pub const FFI_BUFFER_TOO_SMALL: i32 = -3;
+// Generating the mnemonic failed, e.g. the RNG failed
+pub const FFI_GENERATION_FAILED: i32 = -4;
+
+// This generates a new English mnemonic of `word_count` words
+// and writes its sentence into `out` as UTF-8 followed by a NUL
+// byte, so it can be read as a C string. `*out_len` holds the
+// size of `out` when called and the length of the sentence
+// without the NUL byte on success. When the buffer is too small
+// nothing is written and `*out_len` is set to the size it has to
+// be, 24 English words always fit in 256 bytes. The mnemonic is
+// secret, so wipe the buffer once done with it. Our own copy is
+// wiped before we return.
+//
+// Safety: `out_len` must be null or point to a `usize` we can
+// read and write, and `out` must be null or point to at least
+// `*out_len` bytes we can write.
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn generate_c(word_count: u32, out: *mut u8, out_len: *mut usize) -> i32 {
+    if out.is_null() || out_len.is_null() {
+        return FFI_NULL_POINTER;
+    }
+
+    let mut generator = Bip39Generator::with_language(Language::English);
+
+    let mnemonic = match generator.mnemonic_runtime(word_count as usize) {
+        Ok(mnemonic) => mnemonic,
+        Err(error) => {
+            return match error.get_ref().and_then(|error| error.downcast_ref()) {
+                Some(Bip39Error::InvalidWordCount(..)) => FFI_INVALID_WORD_COUNT,
+                _ => FFI_GENERATION_FAILED,
+            }
+        }
+    };
+    let sentence = Zeroizing::new(mnemonic.to_string());
+
+    // Room for the sentence and the NUL byte
+    let needed = sentence.len() + 1;
+    if *out_len < needed {
+        *out_len = needed;
+        return FFI_BUFFER_TOO_SMALL;
+    }
+
+    let out = slice::from_raw_parts_mut(out, needed);
+    out[..sentence.len()].copy_from_slice(sentence.as_bytes());
+    out[sentence.len()] = 0;
+    *out_len = sentence.len();
+
+    FFI_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_c_writes_a_c_string() {
+        let mut buffer = [0xFFu8; 256];
+        let mut length = buffer.len();
+        let result = unsafe { generate_c(24, buffer.as_mut_ptr(), &mut length) };
+        assert_eq!(result, FFI_OK);
+        assert_eq!(buffer[length], 0);
+
+        let sentence = std::str::from_utf8(&buffer[..length]).unwrap();
+        assert_eq!(sentence.parse::<crate::Mnemonic>().unwrap().word_count(), 24);
+
+        let mut small = [0u8; 4];
+        let mut length = small.len();
+        let result = unsafe { generate_c(12, small.as_mut_ptr(), &mut length) };
+        assert_eq!(result, FFI_BUFFER_TOO_SMALL);
+        assert!(length > small.len());
+        assert_eq!(small, [0u8; 4]);
+
+        let mut length = buffer.len();
+        let result = unsafe { generate_c(13, buffer.as_mut_ptr(), &mut length) };
+        assert_eq!(result, FFI_INVALID_WORD_COUNT);
+        let result = unsafe { generate_c(12, std::ptr::null_mut(), &mut length) };
+        assert_eq!(result, FFI_NULL_POINTER);
+    }
+}
@@ -0,0 +1,151 @@
+use alloc::borrow::ToOwned;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
+use unicode_normalization::UnicodeNormalization;
+#[cfg(feature = "std")]
+use zeroize::Zeroizing;
+
+use crate::Bip39Error;
+
+// The languages BIP39 publishes an official wordlist for.
+// Each wordlist is embedded into the binary at compile time
+// using `include_str!()` so generating a mnemonic doesn't
+// depend on a wordlist file being present on disk.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    #[default]
+    English,
+    Japanese,
+    Korean,
+    Spanish,
+    French,
+    Italian,
+    Czech,
+    Portuguese,
+    ChineseSimplified,
+    ChineseTraditional,
+}
+
+impl Language {
+    // Every supported language, handy for looping over
+    // all the wordlists
+    pub const ALL: [Language; 10] = [
+        Language::English,
+        Language::Japanese,
+        Language::Korean,
+        Language::Spanish,
+        Language::French,
+        Language::Italian,
+        Language::Czech,
+        Language::Portuguese,
+        Language::ChineseSimplified,
+        Language::ChineseTraditional,
+    ];
+
+    // This returns the embedded wordlist for the language,
+    // one word per line just like the files published
+    // alongside the BIP39 specification
+    pub fn wordlist(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("english.txt"),
+            Language::Japanese => include_str!("japanese.txt"),
+            Language::Korean => include_str!("korean.txt"),
+            Language::Spanish => include_str!("spanish.txt"),
+            Language::French => include_str!("french.txt"),
+            Language::Italian => include_str!("italian.txt"),
+            Language::Czech => include_str!("czech.txt"),
+            Language::Portuguese => include_str!("portuguese.txt"),
+            Language::ChineseSimplified => include_str!("chinese_simplified.txt"),
+            Language::ChineseTraditional => include_str!("chinese_traditional.txt"),
+        }
+    }
+
+    // The spec requires Japanese mnemonics to be joined with
+    // an ideographic space (U+3000), every other language
+    // uses a plain ASCII space
+    pub fn separator(&self) -> &'static str {
+        match self {
+            Language::Japanese => "\u{3000}",
+            _ => " ",
+        }
+    }
+
+    // The languages written in the Latin alphabet. Their
+    // wordlists are designed so the first four letters of a
+    // word, with any accents removed, are enough to identify it.
+    pub fn is_latin(&self) -> bool {
+        matches!(
+            self,
+            Language::English
+                | Language::Spanish
+                | Language::French
+                | Language::Italian
+                | Language::Czech
+                | Language::Portuguese
+        )
+    }
+
+    // This function works out which language a mnemonic is
+    // written in by checking which embedded wordlist contains
+    // every one of its words. We return `None` when no wordlist
+    // matches or when several unrelated ones do, since we can't
+    // tell which one was meant. The two Chinese wordlists share
+    // many characters so a mnemonic can fit both, in that case
+    // we return the first one that matches. The wordlists are in
+    // NFKD form so each word is lowercased and put in NFKD form
+    // first, otherwise composed Japanese, Korean or Spanish input
+    // would match nothing.
+    #[cfg(feature = "std")]
+    pub fn detect(mnemonic: &str) -> Option<Language> {
+        let words = mnemonic
+            .split_whitespace()
+            .map(|word| Zeroizing::new(word.chars().flat_map(char::to_lowercase).nfkd().collect()))
+            .collect::<Vec<Zeroizing<String>>>();
+
+        if words.is_empty() {
+            return None;
+        }
+
+        // Collect every language whose wordlist has all the words
+        let matches = Language::ALL
+            .into_iter()
+            .filter(|language| {
+                let wordlist = language.wordlist().lines().collect::<HashSet<&str>>();
+                words.iter().all(|word| wordlist.contains(word.as_str()))
+            })
+            .collect::<Vec<Language>>();
+
+        match matches.as_slice() {
+            [language] => Some(*language),
+            [Language::ChineseSimplified, Language::ChineseTraditional] => {
+                Some(Language::ChineseSimplified)
+            }
+            _ => None,
+        }
+    }
+}
+
+// This lets us pick a language by name, e.g. from the command
+// line. Names are case insensitive and the Chinese wordlists
+// are written `chinese-simplified` and `chinese-traditional`.
+impl FromStr for Language {
+    type Err = Bip39Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().replace('_', "-").as_str() {
+            "english" => Ok(Language::English),
+            "japanese" => Ok(Language::Japanese),
+            "korean" => Ok(Language::Korean),
+            "spanish" => Ok(Language::Spanish),
+            "french" => Ok(Language::French),
+            "italian" => Ok(Language::Italian),
+            "czech" => Ok(Language::Czech),
+            "portuguese" => Ok(Language::Portuguese),
+            "chinese-simplified" => Ok(Language::ChineseSimplified),
+            "chinese-traditional" => Ok(Language::ChineseTraditional),
+            _ => Err(Bip39Error::UnknownLanguage(name.to_owned())),
+        }
+    }
+}
@@ -0,0 +1,2695 @@
+// Without the `std` feature we only build the parts of BIP39
+// that need nothing more than `alloc`, see `crypto`
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+#[cfg(feature = "std")]
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "std")]
+use sha2::{Digest, Sha256};
+#[cfg(feature = "std")]
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::File,
+    io::{self, prelude::*},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+#[cfg(feature = "std")]
+use unicode_normalization::{char::canonical_combining_class, UnicodeNormalization};
+#[cfg(feature = "std")]
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+#[cfg(feature = "std")]
+pub mod bip32;
+#[cfg(feature = "std")]
+pub mod bip85;
+pub mod crypto;
+#[cfg(feature = "std")]
+pub mod electrum;
+mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod language;
+#[cfg(feature = "std")]
+mod builder;
+#[cfg(feature = "std")]
+mod mnemonic;
+#[cfg(feature = "std")]
+mod network;
+mod passphrase;
+#[cfg(feature = "std")]
+mod seed;
+#[cfg(feature = "std")]
+pub mod shamir;
+#[cfg(feature = "std")]
+mod wallet;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "std")]
+pub use builder::Bip39GeneratorBuilder;
+// `compute_checksum()` is the pure checksum function every
+// other part of the crate goes through
+pub use crypto::{
+    bits_to_indices, bits_to_indices_with, checksum as compute_checksum, indices_to_bits,
+    indices_to_bits_with, SeedParams,
+};
+pub use error::Bip39Error;
+pub use language::Language;
+pub use passphrase::Passphrase;
+#[cfg(feature = "std")]
+pub use mnemonic::Mnemonic;
+#[cfg(feature = "std")]
+pub use network::{
+    Network, MAINNET_P2PKH_PREFIX, MAINNET_P2SH_PREFIX, MAINNET_WIF_PREFIX, TESTNET_P2PKH_PREFIX,
+    TESTNET_P2SH_PREFIX, TESTNET_WIF_PREFIX,
+};
+#[cfg(feature = "std")]
+pub use seed::Seed;
+#[cfg(feature = "std")]
+pub use wallet::GeneratedWallet;
+
+// Number of iterations to be run by the PBKDF2 for key derivation.
+// This is the value BIP39 mandates, changing it breaks compatibility
+pub const ITERATION_COUNT: u32 = 2048;
+// The word used as a prefix for the salt for our key derivation function
+pub const SALT_PREFIX: &str = "mnemonic";
+// The number of words every BIP39 wordlist contains, one for
+// each value an 11 bit index can take
+pub const WORDLIST_LENGTH: usize = 2048;
+// The number of bits each word of a mnemonic stands for,
+// enough to index any of the 2048 words of a wordlist
+pub const BITS_PER_WORD: usize = 11;
+// The number of random bytes BIP39 allows us to generate a mnemonic from,
+// 128, 160, 192, 224 and 256 bits respectively
+pub const ENTROPY_SIZES: [usize; 5] = [16, 20, 24, 28, 32];
+// The number of bytes in a BIP39 seed, 512 bits
+pub const SEED_LENGTH: usize = 64;
+// Some editors save UTF-8 files with this character in front
+// of the first line, it is not part of the first word
+#[cfg(feature = "std")]
+const BYTE_ORDER_MARK: char = '\u{FEFF}';
+
+// This function tells us how many words we get for `n` bytes
+// of entropy. Each byte is 8 bits, the checksum adds 1 bit for
+// every 32 bits of entropy and each word holds 11 bits, so
+// 16 bytes give 12 words, 20 give 15, 24 give 18, 28 give 21
+// and 32 give 24 words.
+pub const fn word_count(n: usize) -> usize {
+    word_count_with(n, BITS_PER_WORD)
+}
+
+// Same as `word_count()` for words of `bits_per_word` bits.
+// When the bits don't split evenly the last word is padded
+// so we round up.
+pub const fn word_count_with(n: usize, bits_per_word: usize) -> usize {
+    total_bits(n).div_ceil(bits_per_word)
+}
+
+// This function is the reverse of `word_count()`, telling us
+// how many bytes of entropy a mnemonic of `words` words needs,
+// which is the `N` to pass to e.g. `Bip39Generator::mnemonic()`.
+// We get `None` for any count other than 12, 15, 18, 21 or 24.
+pub const fn entropy_bytes_for_words(words: usize) -> Option<usize> {
+    entropy_bytes_for_words_with(words, BITS_PER_WORD)
+}
+
+// Same as `entropy_bytes_for_words()` for words of
+// `bits_per_word` bits
+pub const fn entropy_bytes_for_words_with(words: usize, bits_per_word: usize) -> Option<usize> {
+    // Iterators can't be used in a `const fn` so we loop by hand
+    let mut i = 0;
+    while i < ENTROPY_SIZES.len() {
+        if word_count_with(ENTROPY_SIZES[i], bits_per_word) == words {
+            return Some(ENTROPY_SIZES[i]);
+        }
+        i += 1;
+    }
+
+    None
+}
+
+// This function lists the word counts a mnemonic of words of
+// `bits_per_word` bits can have, one for each entropy size,
+// e.g. "12, 15, 18, 21 or 24" for BIP39. It is meant for
+// messages telling a user which counts we accept.
+pub fn describe_word_counts(bits_per_word: usize) -> String {
+    let counts = ENTROPY_SIZES.map(|n| word_count_with(n, bits_per_word).to_string());
+    let (last, rest) = counts.split_last().expect("there is more than one entropy size");
+
+    format!("{} or {}", rest.join(", "), last)
+}
+
+// This function tells us how many checksum bits BIP39 appends
+// to `entropy_len_bytes` bytes of entropy, one for every 32 bits
+// so 4 bits for 16 bytes up to 8 bits for 32 bytes
+pub const fn checksum_bits(entropy_len_bytes: usize) -> usize {
+    entropy_len_bytes * 8 / 32
+}
+
+// This function tells us how many bits the words of a mnemonic
+// carry for `entropy_len_bytes` bytes of entropy, the entropy
+// bits plus the checksum bits, e.g. 132 bits for 16 bytes
+pub const fn total_bits(entropy_len_bytes: usize) -> usize {
+    entropy_len_bytes * 8 + checksum_bits(entropy_len_bytes)
+}
+
+
+// This function does the work behind `mnemonic_to_entropy()`
+// for any word -> index map, which lets the `Mnemonic` type
+// validate words against the wordlist of its own language.
+#[cfg(feature = "std")]
+pub(crate) fn entropy_from_words(
+    word_index: &HashMap<String, u16>,
+    mnemonic: &str,
+) -> Result<Vec<u8>, Bip39Error> {
+    entropy_from_words_with(word_index, mnemonic, BITS_PER_WORD)
+}
+
+// Same as `entropy_from_words()` for a wordlist with words of
+// `bits_per_word` bits
+#[cfg(feature = "std")]
+pub(crate) fn entropy_from_words_with(
+    word_index: &HashMap<String, u16>,
+    mnemonic: &str,
+    bits_per_word: usize,
+) -> Result<Vec<u8>, Bip39Error> {
+    // Find the index of each word in our wordlist.
+    // A word that is not in the wordlist means the
+    // mnemonic can never be valid. We keep going after
+    // an unknown word so we can report all of them.
+    let mut mnemonic_index = Zeroizing::new(Vec::<u16>::new());
+    let mut problems = Vec::new();
+    let mut words = 0;
+    for word in mnemonic.split_whitespace() {
+        match word_index.get(nfkd_word(word).as_str()) {
+            Some(&index) => mnemonic_index.push(index),
+            None => problems.push(Bip39Error::UnknownWord(word.to_owned())),
+        }
+        words += 1;
+    }
+
+    // The word count doesn't depend on the words being known
+    if !ENTROPY_SIZES
+        .iter()
+        .any(|&n| word_count_with(n, bits_per_word) == words)
+    {
+        problems.push(Bip39Error::InvalidWordCount(words, bits_per_word));
+    }
+
+    // The checksum can only be computed once we have every
+    // index of a mnemonic of the right length, this also
+    // checks the word count again for us
+    match problems.len() {
+        0 => crypto::indices_to_entropy_with(&mnemonic_index, bits_per_word),
+        1 => Err(problems.remove(0)),
+        _ => Err(Bip39Error::InvalidMnemonic(problems)),
+    }
+}
+
+
+// This function reads a passphrase out of the environment
+// variable `variable` into a `Passphrase` that is wiped when dropped.
+// The environment of our process keeps its own copy which we
+// can't wipe, so callers that care should unset the variable
+// as soon as they can.
+#[cfg(feature = "std")]
+pub fn passphrase_from_env(variable: &str) -> io::Result<Passphrase> {
+    match std::env::var(variable) {
+        Ok(passphrase) => Ok(Passphrase::new(passphrase)),
+        Err(std::env::VarError::NotPresent) => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("environment variable `{}` is not set", variable),
+        )),
+        Err(std::env::VarError::NotUnicode(_)) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("environment variable `{}` is not valid unicode", variable),
+        )),
+    }
+}
+
+// This function turns a word into the key we compare on when
+// accent tolerant matching is on: the word is NFKD normalized
+// so accents become separate combining marks which we drop,
+// then lowercased and cut down to its first four letters,
+// e.g. "Ábaco" and "abac" both become "abac"
+#[cfg(feature = "std")]
+fn accent_key(word: &str) -> Zeroizing<String> {
+    Zeroizing::new(
+        word.nfkd()
+            .filter(|c| canonical_combining_class(*c) == 0)
+            .flat_map(char::to_lowercase)
+            .take(4)
+            .collect(),
+    )
+}
+
+// This function puts a word into NFKD form, the form the BIP39
+// wordlists are published in. Japanese or Korean typed on most
+// keyboards comes out composed (NFC) and would otherwise never
+// match its entry in the wordlist, python-mnemonic does the same.
+#[cfg(feature = "std")]
+fn nfkd_word(word: &str) -> Zeroizing<String> {
+    Zeroizing::new(word.nfkd().collect())
+}
+
+// This function computes the Levenshtein distance between two
+// words, the number of single character insertions, deletions
+// and substitutions needed to turn one into the other. We only
+// keep the previous row of the usual table since that is all
+// each new row needs.
+#[cfg(feature = "std")]
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<char>>();
+    let mut previous = (0..=b.len()).collect::<Vec<usize>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+// This function returns the CSPRNG every fresh mnemonic, Shamir
+// split and Electrum seed takes its randomness from. With the
+// default `chacha` feature that is ChaCha20 seeded from the OS,
+// with `osrng` we read every byte from the OS RNG directly. Any
+// other source, e.g. a hardware RNG, can be passed to
+// `Entropy::from_rng()` as long as it implements `RngCore`.
+#[cfg(feature = "osrng")]
+pub(crate) fn system_rng() -> impl RngCore + CryptoRng {
+    rand_core::OsRng
+}
+
+#[cfg(all(feature = "chacha", not(feature = "osrng")))]
+pub(crate) fn system_rng() -> impl RngCore + CryptoRng {
+    use rand_core::SeedableRng;
+
+    rand_chacha::ChaCha20Rng::from_entropy()
+}
+
+#[cfg(all(feature = "std", not(any(feature = "chacha", feature = "osrng"))))]
+compile_error!("the `std` feature needs an RNG, enable either `chacha` or `osrng`");
+
+// This struct takes a constant `N` as a generic
+// enabling one to specify a variable length for the bytes generated.
+// The bytes are wiped from memory once the entropy is dropped.
+#[cfg(feature = "std")]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Zeroize, ZeroizeOnDrop)]
+pub struct Entropy<const N: usize>([u8; N]);
+
+#[cfg(feature = "std")]
+impl<const N: usize> Entropy<N> {
+    // This method generates the bytes 
+    pub fn generate() -> Self {
+        // Instantiate our cryptographically secure random byte generation algorithm
+        let mut rng = system_rng();
+
+        Entropy::from_rng(&mut rng)
+    }
+
+    // This method generates the bytes using the random number
+    // generator we pass in. Passing a seeded RNG such as
+    // `ChaCha20Rng::seed_from_u64()` gives the same bytes
+    // every time, which is what we need for reproducible tests.
+    pub fn from_rng(rng: &mut impl RngCore) -> Self {
+        // Create a zero filled buffer to hold our bytes
+        let mut buffer = [0u8; N];
+        // Fill our buffer with random bytes
+        rng.fill_bytes(&mut buffer);
+
+        // Return our buffer
+        Self(buffer)
+    }
+
+    // The random bytes
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+// This gives back the entropy a mnemonic was made from, e.g.
+// `Entropy::<16>::try_from(&mnemonic)` for 12 words. Like
+// `Mnemonic::entropy_hex()` it uses the indexes of the words in
+// the wordlist the mnemonic came from, so a custom wordlist
+// gives back its own entropy. It fails like that method does,
+// or with `Bip39Error::InvalidWordCount` when the mnemonic holds
+// a different amount of entropy than `N` bytes.
+#[cfg(feature = "std")]
+impl<const N: usize> TryFrom<&Mnemonic> for Entropy<N> {
+    type Error = Bip39Error;
+
+    fn try_from(mnemonic: &Mnemonic) -> Result<Self, Self::Error> {
+        let entropy = mnemonic.entropy()?;
+        let bytes = <[u8; N]>::try_from(entropy.as_slice())
+            .map_err(|_| {
+                Bip39Error::InvalidWordCount(mnemonic.word_count(), mnemonic.bits_per_word())
+            })?;
+
+        Ok(Self(bytes))
+    }
+}
+
+// The entropy is as secret as the mnemonic it becomes, so debug
+// output only tells how many bytes there are, never what they are.
+// `as_bytes()` is there for when the bytes really are needed.
+#[cfg(feature = "std")]
+impl<const N: usize> fmt::Debug for Entropy<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Entropy(<redacted {} bytes>)", N)
+    }
+}
+
+
+#[cfg(feature = "std")]
+#[derive(Default)]
+pub struct Bip39Generator {
+    // This holds all our indexes that we will use to fetch
+    // our word from the word list 
+    // with each index corresponding to an index
+    // from our wordlist contained in a Vec<word>
+    // These are secret so they are wiped when the generator is dropped
+    mnemonic_index: Zeroizing<Vec<u16>>,
+    // This field holds the random bytes with our checksum
+    // bytes appended to the end
+    // This is wiped when the generator is dropped
+    appended: Zeroizing<Vec<u8>>,
+    // This contains a path to our wordlist file, when there
+    // is no path we use the embedded wordlist of `language`
+    path: Option<PathBuf>,
+    // The language of our wordlist which also decides
+    // how the words of a mnemonic are joined together
+    language: Language,
+    // This caches our wordlist after it is read from `path`
+    // the first time so that generating or validating more
+    // than once doesn't hit the filesystem again
+    wordlist: OnceLock<Vec<String>>,
+    // This maps each word of our wordlist back to its index
+    // so going from words to indexes is a single lookup
+    // instead of scanning all 2048 words for every word
+    word_index: OnceLock<HashMap<String, u16>>,
+    // When this is set words of a Spanish, French, Italian,
+    // Czech or Portuguese mnemonic are matched on their first
+    // four letters without accents, see `accent_tolerant()`
+    accent_tolerant: bool,
+    // This maps the `accent_key()` of each word of our wordlist
+    // back to its index, built the first time we need it
+    accent_index: OnceLock<HashMap<String, u16>>,
+    // The network the keys of our seeds are meant for
+    network: Network,
+    // The number of PBKDF2 iterations to derive our seeds with,
+    // `None` means the `ITERATION_COUNT` BIP39 requires
+    iterations: Option<u32>,
+    // When this is set generating a mnemonic reads only the
+    // words it needs instead of loading the whole wordlist,
+    // see `streaming()`
+    streaming: bool,
+    // The number of bits each word stands for, `None` means the
+    // `BITS_PER_WORD` of BIP39, see `bits_per_word()`
+    bits_per_word: Option<usize>,
+}
+
+// The indexes and bytes of the last mnemonic we generated are
+// still in the generator, so debug output leaves them out along
+// with the cached wordlists, which are public but 2048 words long
+#[cfg(feature = "std")]
+impl fmt::Debug for Bip39Generator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bip39Generator")
+            .field("path", &self.path)
+            .field("language", &self.language)
+            .field("accent_tolerant", &self.accent_tolerant)
+            .field("network", &self.network)
+            .field("iterations", &self.iterations())
+            .field("streaming", &self.streaming)
+            .field("bits_per_word", &self.bits_per_word())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Bip39Generator {
+    // This method takes an argument `path_to_wordlist` which
+    // is a path to the wordlist we downloaded
+    // where the path is anything that implements the trait
+    // AsRef<Path> meaning we pass any data type as convert it 
+    // to a path using the `.as_ref()` method as long as that
+    // data type implements the `AsRef<Path>` trait.
+    pub fn new(path_to_wordlist: impl AsRef<Path>) -> Self {
+        Self {
+            // Convert `path_to_wordlist` argument to a path
+            // using `.as_ref()` method and convert it
+            // to a `std::path::PathBuf` using the `.to_path_buf()`
+            path: Some(path_to_wordlist.as_ref().to_path_buf()),
+             // All other fields can hold default values
+            // and we can call this method since 
+            // we derived `Default` values using `#[derive(Default)]` 
+            // on our struct 
+            ..Default::default()
+        }
+    }
+
+    // This method creates a generator from words we already
+    // have in memory, e.g. a custom list on a target without
+    // a filesystem. The words are checked the same way as a
+    // wordlist read from disk and words are joined with a
+    // plain space like every language except Japanese. The
+    // length of the list decides the bits per word, so it must
+    // be a power of two, 2048 words give the 11 bits of BIP39
+    // and e.g. 1024 words give 10 bits. We pack whole bits into
+    // each index, which is why a list like the 1626 words of old
+    // Electrum seeds (which count in base 1626) is refused.
+    pub fn from_wordlist(words: Vec<String>) -> io::Result<Self> {
+        // Empty lines at the end are dropped by `check_wordlist()`
+        // so they don't count towards the length
+        let length = words.len() - words.iter().rev().take_while(|word| word.is_empty()).count();
+        if !length.is_power_of_two() || !(2..=1 << 16).contains(&length) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "invalid wordlist in memory: expected a power of two between 2 and 65536 \
+                     words so each word holds whole bits, but found {}",
+                    length
+                ),
+            ));
+        }
+
+        let wordlist = Bip39Generator::check_wordlist(words, "in memory", length)?;
+
+        Ok(Self {
+            wordlist: OnceLock::from(wordlist),
+            bits_per_word: Some(length.trailing_zeros() as usize),
+            ..Default::default()
+        })
+    }
+
+    // This method creates a generator that uses the wordlist
+    // embedded for `language` instead of reading one from disk
+    pub fn with_language(language: Language) -> Self {
+        Self {
+            language,
+            ..Default::default()
+        }
+    }
+
+    // This method turns on accent tolerant matching of the words
+    // of a mnemonic we validate or recover. Users often type
+    // Spanish or French words without their accents, e.g.
+    // "abaco" for "ábaco", and BIP39 guarantees the first four
+    // letters of a word are unique within a Latin wordlist, so
+    // we can still tell which word they meant. Each word is
+    // replaced by the wordlist word it matches before the seed
+    // is derived, since the seed depends on the exact spelling.
+    // English is always matched exactly, as are the languages
+    // not written in the Latin alphabet.
+    pub fn accent_tolerant(mut self, enabled: bool) -> Self {
+        self.accent_tolerant = enabled;
+        self
+    }
+
+    // This method turns on streaming the wordlist while we
+    // generate. Instead of keeping all 2048 words and the word
+    // -> index map in memory we sort the indexes of our words
+    // and read the wordlist once from the top, keeping only the
+    // lines we need, so memory stays bounded by the length of
+    // the mnemonic. This is meant for embedded and WASM builds,
+    // validating and recovering still load the full wordlist.
+    pub fn streaming(mut self, enabled: bool) -> Self {
+        self.streaming = enabled;
+        self
+    }
+
+    // This returns a builder to set the language, network,
+    // iteration count and wordlist path in one go
+    pub fn builder() -> Bip39GeneratorBuilder {
+        Bip39GeneratorBuilder::new()
+    }
+
+    // The network the keys of our seeds are meant for, which is
+    // what `master_xprv()` and `account_xpub()` serialize for
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    // This serializes the BIP32 master key of `seed` for our
+    // network, an `xprv` on mainnet or a `tprv` on testnet and
+    // regtest. It is as secret as the seed itself.
+    pub fn master_xprv(&self, seed: &Seed) -> Result<String, Bip39Error> {
+        Ok(bip32::ExtendedPrivKey::new_master(seed.as_bytes())?.to_xprv(self.network))
+    }
+
+    // This derives the account xpub of `seed` for `purpose`
+    // (44, 49 or 84) on our network, using the coin type of the
+    // network, e.g. `m/84'/0'/0'` as a `zpub` on mainnet and
+    // `m/84'/1'/0'` as a `vpub` on testnet,
+    // see `ExtendedPrivKey::account_xpub()`
+    pub fn account_xpub(
+        &self,
+        seed: &Seed,
+        purpose: u32,
+        account: u32,
+    ) -> Result<String, Bip39Error> {
+        bip32::ExtendedPrivKey::new_master(seed.as_bytes())?.account_xpub(
+            purpose,
+            self.network.coin_type(),
+            account,
+            self.network,
+        )
+    }
+
+    // The number of PBKDF2 iterations our seeds are derived
+    // with, `ITERATION_COUNT` unless the builder changed it
+    pub fn iterations(&self) -> u32 {
+        self.iterations.unwrap_or(ITERATION_COUNT)
+    }
+
+    // How our seeds are derived, e.g. to show "2048 rounds of
+    // HMAC-SHA512" while `recover()` is busy stretching the mnemonic
+    pub fn seed_params(&self) -> SeedParams {
+        SeedParams {
+            iterations: self.iterations(),
+            ..SeedParams::BIP39
+        }
+    }
+
+    // The number of bits each word of our mnemonics stands for,
+    // the 11 bits of BIP39 unless the builder changed it for a
+    // custom scheme with a wordlist of a different size
+    pub fn bits_per_word(&self) -> usize {
+        self.bits_per_word.unwrap_or(BITS_PER_WORD)
+    }
+
+    // The number of words of our wordlist. Once it is loaded that
+    // is its length, before then it is the length it must have,
+    // one word for each value a word of `bits_per_word()` bits
+    // can take, so 2048 for BIP39
+    pub fn wordlist_length(&self) -> usize {
+        self.wordlist
+            .get()
+            .map_or(1 << self.bits_per_word(), Vec::len)
+    }
+    // The `<const N: usize>` in our method allows us
+// to get the number of bytes to generate for our
+// seed. eg. 32 bytes (256 bits) or 16 bytes (128 bits)
+  
+// This method allows us to generate a seed without
+// a passphrase.
+  pub fn insecure_mnemonic<const N: usize>(&mut self) -> io::Result<(Mnemonic, Seed)> {
+    // This calls the `mnemonic()` method (which we will implement)
+     // in order to get our mnemonic passing the number
+    // of bytes using `::<N>`
+    let mnemonic = self.mnemonic::<N>()?;
+    // We call this method to get our seed from the
+    // key derivation method implemented within
+    // the `seed()` method where we pass our `mnemonic` variable
+    // above and an `Option::None` indicating we don't 
+    // want to generate our seed using a passphrase.
+    let seed = Zeroizing::new(Bip39Generator::seed_with_iterations(
+        &Zeroizing::new(mnemonic.to_string()),
+        Option::None,
+        self.iterations(),
+    )?);
+    let seed = Seed::from_bytes(&seed)?;
+
+    // We then return our `mnemonic` and `seed`
+    Ok((mnemonic, seed))
+    }
+
+    // This method allows us to generate a seed with
+    // a passphrase as a requirement. 
+    // An empty passphrase gives the same seed as no passphrase
+    // at all, so we refuse it rather than let a caller believe
+    // the seed is protected, use `insecure_mnemonic()` instead.
+    // Other than that there is no limit on the length of the
+    // passphrase, PBKDF2 hashes a passphrase of any length.
+    pub fn secure_mnemonic<const N: usize>(
+        &mut self,
+        passphrase: &str,
+    ) -> io::Result<(Mnemonic, Seed)> {
+        if passphrase.is_empty() {
+            return Err(Bip39Error::EmptyPassphrase.into());
+        }
+
+        // Same as the previous method
+        let mnemonic = self.mnemonic::<N>()?;
+        // This section is also the same as the previous
+        // method but we pass an `Option::Some(passphrase)`
+        // to indicate we intent to generate our seed using a passphrase
+        let seed = Zeroizing::new(Bip39Generator::seed_with_iterations(
+            &Zeroizing::new(mnemonic.to_string()),
+            Option::Some(passphrase),
+            self.iterations(),
+        )?);
+        let seed = Seed::from_bytes(&seed)?;
+
+        // We then return our `mnemonic` and `seed`
+        Ok((mnemonic, seed))
+    }
+
+    // These methods generate a mnemonic of a given length and
+    // its seed without a passphrase, picking the number of
+    // bytes of entropy for us so we don't need `::<N>`
+    pub fn generate_12_words(&mut self) -> io::Result<(Mnemonic, Seed)> {
+        self.insecure_mnemonic::<16>()
+    }
+
+    pub fn generate_15_words(&mut self) -> io::Result<(Mnemonic, Seed)> {
+        self.insecure_mnemonic::<20>()
+    }
+
+    pub fn generate_18_words(&mut self) -> io::Result<(Mnemonic, Seed)> {
+        self.insecure_mnemonic::<24>()
+    }
+
+    pub fn generate_21_words(&mut self) -> io::Result<(Mnemonic, Seed)> {
+        self.insecure_mnemonic::<28>()
+    }
+
+    pub fn generate_24_words(&mut self) -> io::Result<(Mnemonic, Seed)> {
+        self.insecure_mnemonic::<32>()
+    }
+
+    // This method generates a mnemonic from `N` bytes of fresh
+    // entropy and derives its seed with the optional passphrase,
+    // handing back the entropy, mnemonic and seed together so we
+    // don't have to derive the seed again or recover the entropy
+    // from the words for a backup
+    pub fn generate_full<const N: usize>(
+        &mut self,
+        passphrase: Option<&str>,
+    ) -> io::Result<GeneratedWallet> {
+        let entropy = Entropy::<{ N }>::generate();
+        let mnemonic = self.from_entropy(entropy.as_bytes())?;
+
+        let seed = Zeroizing::new(Bip39Generator::seed_array_with_iterations(
+            &Zeroizing::new(mnemonic.to_string()),
+            passphrase,
+            self.iterations(),
+        )?);
+
+        Ok(GeneratedWallet {
+            entropy: entropy.as_bytes().to_vec(),
+            mnemonic,
+            seed: Seed::new(*seed),
+        })
+    }
+
+    // Same as `generate_full()` but makes `count` wallets in one
+    // call, e.g. when provisioning many wallets at once. Each one
+    // gets its own fresh entropy from the RNG and the same
+    // passphrase, while the wordlist is only loaded once for all
+    // of them. Every seed costs a full PBKDF2 run so this takes a
+    // few milliseconds per wallet.
+    pub fn generate_many<const N: usize>(
+        &mut self,
+        count: usize,
+        passphrase: Option<&str>,
+    ) -> io::Result<Vec<GeneratedWallet>> {
+        let mut wallets = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            wallets.push(self.generate_full::<N>(passphrase)?);
+        }
+
+        Ok(wallets)
+    }
+
+    // Same as the methods above but the number of words is only
+    // known at runtime, e.g. when it comes from the user, see
+    // `mnemonic_runtime()`
+    pub fn generate_runtime(&mut self, words: usize) -> io::Result<(Mnemonic, Seed)> {
+        let mnemonic = self.mnemonic_runtime(words)?;
+
+        let seed = Zeroizing::new(Bip39Generator::seed_with_iterations(
+            &Zeroizing::new(mnemonic.to_string()),
+            None,
+            self.iterations(),
+        )?);
+        let seed = Seed::from_bytes(&seed)?;
+
+        Ok((mnemonic, seed))
+    }
+
+    // Same as `generate_full()` for a number of words only known
+    // at runtime, see `mnemonic_runtime()`
+    pub fn generate_full_runtime(
+        &mut self,
+        words: usize,
+        passphrase: Option<&str>,
+    ) -> io::Result<GeneratedWallet> {
+        let mnemonic = self.mnemonic_runtime(words)?;
+        let entropy = mnemonic.entropy()?;
+
+        let seed = Zeroizing::new(Bip39Generator::seed_array_with_iterations(
+            &Zeroizing::new(mnemonic.to_string()),
+            passphrase,
+            self.iterations(),
+        )?);
+
+        Ok(GeneratedWallet {
+            entropy: entropy.to_vec(),
+            mnemonic,
+            seed: Seed::new(*seed),
+        })
+    }
+
+    // This method only needs to read `Self` and hands out
+    // the cached wordlist, reading it from disk on first use
+    fn load_wordlist(&self) -> io::Result<&[String]> {
+        // Return the wordlist straight away if
+        // we have already loaded it before
+        if let Some(wordlist) = self.wordlist.get() {
+            return Ok(wordlist);
+        }
+
+        let wordlist = match &self.path {
+            Some(path) => {
+                // open the file using the path we passed
+                // when instantiating our struct 
+                // using `Bip39Generator::new()`
+                let file = File::open(path)?;
+                // Create a buffer so that we can efficiently readd
+                // our file
+                let reader: io::BufReader<File> = io::BufReader::new(file);
+
+                // Create a Vector to hold our wordlist
+                let mut wordlist = Vec::<String>::new();
+
+                // Read each line
+                for line in reader.lines() {
+                    // Trim surrounding whitespace so that lists saved
+                    // on Windows (with a trailing `\r`) still work,
+                    // handling any I/O errors using `?`
+                    wordlist.push(line?.trim().to_owned());
+                }
+
+                Bip39Generator::check_wordlist(
+                    wordlist,
+                    &path.display().to_string(),
+                    self.wordlist_length(),
+                )?
+            }
+            // Without a path we split the embedded wordlist
+            // of our language into words instead
+            None => {
+                let wordlist = self
+                    .language
+                    .wordlist()
+                    .lines()
+                    .map(|line| line.trim().to_owned())
+                    .collect::<Vec<String>>();
+
+                Bip39Generator::check_wordlist(
+                    wordlist,
+                    &format!("{:?}", self.language),
+                    self.wordlist_length(),
+                )?
+            }
+        };
+
+        // Cache our vector of word list and return it
+        Ok(self.wordlist.get_or_init(|| wordlist))
+    }
+
+    // Same as loading the wordlist the first time it is needed
+    // but reading the file with `tokio::fs`, so an async service
+    // doesn't block its executor on the filesystem. The wordlist
+    // is cached just the same, so generating or validating
+    // afterwards never touches the disk. Embedded wordlists
+    // need no I/O and are simply split into words.
+    #[cfg(feature = "tokio")]
+    pub async fn load_wordlist_async(&self) -> io::Result<&[String]> {
+        if let Some(wordlist) = self.wordlist.get() {
+            return Ok(wordlist);
+        }
+
+        let path = match &self.path {
+            Some(path) => path,
+            None => return self.load_wordlist(),
+        };
+
+        // Trim each line just like the sync path does
+        let contents = tokio::fs::read_to_string(path).await?;
+        let wordlist = contents
+            .lines()
+            .map(|line| line.trim().to_owned())
+            .collect::<Vec<String>>();
+
+        let wordlist = Bip39Generator::check_wordlist(
+            wordlist,
+            &path.display().to_string(),
+            self.wordlist_length(),
+        )?;
+
+        Ok(self.wordlist.get_or_init(|| wordlist))
+    }
+
+    // This method makes sure a wordlist we loaded from `source`
+    // can safely be indexed by our indexes, which means it must
+    // hold exactly `expected_length` words
+    fn check_wordlist(
+        mut wordlist: Vec<String>,
+        source: &str,
+        expected_length: usize,
+    ) -> io::Result<Vec<String>> {
+        // A byte order mark or empty lines at the end of the file
+        // are left behind by editors and are no words, so we drop
+        // them before indexing anything
+        if let Some(first) = wordlist.first_mut() {
+            if let Some(word) = first.strip_prefix(BYTE_ORDER_MARK) {
+                *first = word.trim().to_owned();
+            }
+        }
+        while wordlist.last().is_some_and(|word| word.is_empty()) {
+            wordlist.pop();
+        }
+
+        // An empty line would shift every word after it
+        // to the wrong index so we refuse the wordlist
+        if let Some(line_number) = wordlist.iter().position(|word| word.is_empty()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid wordlist {}: line {} is empty", source, line_number + 1),
+            ));
+        }
+
+        // Every index we compute has `bits_per_word` bits (11 for
+        // BIP39) so the wordlist must contain exactly
+        // `expected_length` words, 2^bits_per_word, otherwise
+        // looking up a word could go out of bounds
+        if wordlist.len() != expected_length {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "invalid wordlist {}: expected {} words but found {}",
+                    source,
+                    expected_length,
+                    wordlist.len()
+                ),
+            ));
+        }
+
+        // Return our vector of word list
+        Ok(wordlist)
+    }
+
+    // This method builds the word -> index map from our
+    // wordlist the first time it is needed and caches it
+    pub(crate) fn load_word_index(&self) -> io::Result<&HashMap<String, u16>> {
+        if let Some(word_index) = self.word_index.get() {
+            return Ok(word_index);
+        }
+
+        // Pair each word with its position in the wordlist,
+        // the wordlist is 2048 words so every index fits in a `u16`.
+        // The keys are in NFKD form so a wordlist file saved in
+        // NFC still matches the words we look up.
+        let word_index = self
+            .load_wordlist()?
+            .iter()
+            .enumerate()
+            .map(|(index, word)| (nfkd_word(word).to_string(), index as u16))
+            .collect::<HashMap<String, u16>>();
+
+        Ok(self.word_index.get_or_init(|| word_index))
+    }
+
+    // This wipes the indexes and bytes of the last mnemonic we
+    // generated, leaving the cached wordlists alone. Every
+    // generation starts with it, otherwise the indexes of the
+    // next mnemonic would be appended to those of the last one.
+    fn reset(&mut self) {
+        self.mnemonic_index.zeroize();
+        self.appended.zeroize();
+    }
+
+     // Here we pass our generated random bytes as `entropy` argument
+    fn generate_checksum(&mut self, entropy: &[u8]) -> &mut Self {
+        // Compute the checksum bits of our entropy, `from_entropy()`
+        // already checked that its length is one BIP39 allows
+        let significant =
+            compute_checksum(entropy).expect("entropy has one of the BIP39 sizes");
+
+        let mut appended = Zeroizing::new(entropy.to_vec());
+        // We then append our checksum to our random
+        appended.push(significant);
+
+        // We now assign our appended bytes to the `appended`
+        // field of our `Bip39Generator` struct which is `Self`
+        self.appended = appended;
+
+        self
+    }
+
+         // We pass a mutable to self since we want to
+    // add the result of this computation to `Self`.
+    // `entropy_len` is the number of bytes of entropy we passed
+    // to `generate_checksum()`, so `appended` must hold exactly
+    // those bytes plus the one checksum byte. Anything else means
+    // the steps were wired up wrong, e.g. `generate_checksum()`
+    // was skipped, and would quietly give us too few or too many
+    // words, so we panic instead.
+    fn compute(&mut self, entropy_len: usize) -> &mut Self {
+        assert!(
+            ENTROPY_SIZES.contains(&entropy_len) && self.appended.len() == entropy_len + 1,
+            "expected {} bytes of entropy and 1 checksum byte but `appended` holds {} bytes",
+            entropy_len,
+            self.appended.len()
+        );
+
+        // This vector will hold the binary 
+        // representation of each byte in the `appended` vector.
+      let mut bits = Zeroizing::new(vec![]);
+
+      // This line starts a loop that iterates over each byte in the `self.appended` vector.
+      for &byte in self.appended.iter() {
+          // This line starts a nested loop that 
+          // counts backwards from 7 to 0. 
+          // The variable `i` represents the position of 
+          // the bit we're interested in within 
+          // the current byte.
+          for i in (0..8).rev() {
+          /*
+            This line does three things:
+             - `byte >> i`: This is a right bitwise shift operation. 
+                            It moves the bits in `byte` `i` places to the right. 
+                            The bit at position `i` is now at position 0.
+             - `(byte >> i) & 1u8`: This is a bitwise AND operation with `1u8` (which is `1` in binary). 
+                                    This operation effectively masks all the bits in `byte` except for the one at position 0.
+             - `bits.push((byte >> i) & 1u8 == 1);`: This pushes `true` if the bit at position 0 is `1` 
+                                                      and `false` otherwise into the `bits` vector.
+            */            
+              bits.push((byte >> i) & 1u8 == 1);
+          }
+      }
+
+    // The last byte of `appended` only holds as many checksum
+    // bits as the entropy needs (4 to 8) in its high bits, the
+    // rest is padding. We keep exactly the entropy and checksum
+    // bits so they split evenly into 11 bit indexes. Words of a
+    // custom size may need zero bits to fill up the last word.
+    bits.truncate(total_bits(entropy_len));
+    let bits_per_word = self.bits_per_word();
+    bits.resize(word_count_with(entropy_len, bits_per_word) * bits_per_word, false);
+
+    let indices = Zeroizing::new(bits_to_indices_with(&bits, bits_per_word));
+    self.mnemonic_index.extend_from_slice(&indices);
+
+        self
+    }
+
+    // We pass our mnemonic and an optional passphrase
+    pub fn seed(mnemonic: &str, passphrase: Option<&str>) -> io::Result<Vec<u8>> {
+        let mut wallet_seed = Bip39Generator::seed_array(mnemonic, passphrase)?;
+
+        // We copy out our seed and wipe the array
+        // on the stack before returning it
+        let seed = wallet_seed.to_vec();
+        wallet_seed.zeroize();
+
+        Ok(seed)
+    }
+
+    // Same as `seed()` but returns the 64 bytes as an array
+    // instead of allocating a `Vec`. The array is a plain copy
+    // so callers should `zeroize()` it once they are done.
+    pub fn seed_array(mnemonic: &str, passphrase: Option<&str>) -> io::Result<[u8; SEED_LENGTH]> {
+        Bip39Generator::seed_array_with_iterations(mnemonic, passphrase, ITERATION_COUNT)
+    }
+
+    // Same as `seed()` but lets us choose how many PBKDF2
+    // iterations to run instead of the 2048 BIP39 requires.
+    // This is only meant for research into hardened variants,
+    // any value other than `ITERATION_COUNT` gives a seed that
+    // no other BIP39 wallet will reproduce from the same mnemonic.
+    pub fn seed_with_iterations(
+        mnemonic: &str,
+        passphrase: Option<&str>,
+        iterations: u32,
+    ) -> io::Result<Vec<u8>> {
+        let mut wallet_seed =
+            Bip39Generator::seed_array_with_iterations(mnemonic, passphrase, iterations)?;
+
+        let seed = wallet_seed.to_vec();
+        wallet_seed.zeroize();
+
+        Ok(seed)
+    }
+
+    // Same as `seed()` but the PBKDF2 salt starts with `prefix`
+    // instead of `SALT_PREFIX`. WARNING: this breaks BIP39
+    // interoperability. Every other wallet salts with "mnemonic",
+    // so a seed derived with any other prefix can't be recovered
+    // anywhere else from the same mnemonic. It is only meant for
+    // research into alternate schemes, never for real funds.
+    pub fn seed_with_prefix(
+        mnemonic: &str,
+        passphrase: Option<&str>,
+        prefix: &str,
+    ) -> io::Result<Vec<u8>> {
+        let mut wallet_seed =
+            crypto::derive_seed_with_prefix(mnemonic, passphrase, prefix, ITERATION_COUNT)?;
+
+        let seed = wallet_seed.to_vec();
+        wallet_seed.zeroize();
+
+        Ok(seed)
+    }
+
+    // This is where the seed is actually derived, both
+    // `seed()` and `seed_with_iterations()` end up here
+    pub fn seed_array_with_iterations(
+        mnemonic: &str,
+        passphrase: Option<&str>,
+        iterations: u32,
+    ) -> io::Result<[u8; SEED_LENGTH]> {
+        Ok(crypto::derive_seed(mnemonic, passphrase, iterations)?)
+    }
+
+    // This checks that a mnemonic and passphrase reproduce a
+    // seed we stored earlier, e.g. to confirm a backup was
+    // written down correctly. We derive the seed just like
+    // `seed()` and compare it in constant time with `subtle` so
+    // the time taken doesn't leak how much of the seed matched.
+    // A mismatch is `Ok(false)`, errors are only returned when
+    // no seed can be derived at all.
+    pub fn verify_seed(mnemonic: &str, passphrase: Option<&str>, expected: &Seed) -> io::Result<bool> {
+        let seed = Seed::new(Bip39Generator::seed_array(mnemonic, passphrase)?);
+
+        Ok(seed.ct_eq(expected))
+    }
+
+    pub fn mnemonic<const N: usize>(&mut self) -> io::Result<Mnemonic> {
+        // This generates the number of random bits we need
+        let entropy = Entropy::<{ N }>::generate();
+
+        // Build the mnemonic from our freshly generated bytes
+        self.from_entropy(&entropy.0)
+    }
+
+    // Same as `mnemonic()` but the number of words is only known
+    // at runtime, e.g. from a command line argument or across the
+    // C and JavaScript bindings where const generics don't exist.
+    // Any count our `bits_per_word()` can't give, i.e. other than
+    // 12, 15, 18, 21 or 24 for BIP39, is rejected with
+    // `Bip39Error::InvalidWordCount`. Every runtime word count goes
+    // through here so nothing else has to map counts to sizes.
+    pub fn mnemonic_runtime(&mut self, words: usize) -> io::Result<Mnemonic> {
+        let bits_per_word = self.bits_per_word();
+        let length = entropy_bytes_for_words_with(words, bits_per_word)
+            .ok_or(Bip39Error::InvalidWordCount(words, bits_per_word))?;
+
+        // Room for the largest entropy size, we only fill and use
+        // the `length` bytes we need
+        let mut entropy = Zeroizing::new([0u8; 32]);
+        system_rng().fill_bytes(&mut entropy[..length]);
+
+        self.from_entropy(&entropy[..length])
+    }
+
+    // Same as `mnemonic()` but gives back just the words in order,
+    // for code that wants to handle them one by one. Nothing is
+    // joined or split so each word is exactly as the wordlist
+    // spells it. `Mnemonic::to_string()` joins them the way the
+    // language expects, with an ideographic space (U+3000) for
+    // Japanese and a plain space otherwise. Unlike a `Mnemonic`
+    // the `Vec` is not wiped when dropped, so `zeroize()` it
+    // once done with the words.
+    pub fn mnemonic_words<const N: usize>(&mut self) -> io::Result<Vec<String>> {
+        let mnemonic = self.mnemonic::<N>()?;
+
+        Ok(mnemonic.words().map(str::to_owned).collect())
+    }
+
+    // Same as `mnemonic()` but the entropy comes from the random
+    // number generator we pass in instead of a fresh CSPRNG.
+    // Only use a deterministically seeded RNG for testing, the
+    // mnemonic is only as secret as the seed of the RNG.
+    pub fn with_rng<const N: usize>(&mut self, rng: &mut impl RngCore) -> io::Result<Mnemonic> {
+        let entropy = Entropy::<{ N }>::from_rng(rng);
+
+        self.from_entropy(&entropy.0)
+    }
+
+    // This gives the same mnemonic for the same `seed` every time,
+    // a fixture for tests of BIP32, BIP85 or addresses that need a
+    // stable mnemonic. The entropy comes from ChaCha20 seeded
+    // with `seed` so anyone who knows the `u64` knows the words.
+    // It only exists for our own tests or with the `testing`
+    // feature so it can't end up generating real wallets.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn from_seeded_rng<const N: usize>(&mut self, seed: u64) -> io::Result<Mnemonic> {
+        use rand_core::SeedableRng;
+
+        self.with_rng::<N>(&mut rand_chacha::ChaCha20Rng::seed_from_u64(seed))
+    }
+
+    // This method smoke tests the generator, e.g. on a new
+    // device. It generates a mnemonic from fresh entropy and
+    // runs every inverse operation on it: the checksum must be
+    // valid, the words must give back the same entropy and
+    // recovering must derive the same seed as generating did.
+    // We return `false` as soon as any of them disagree.
+    pub fn self_test<const N: usize>(&mut self) -> io::Result<bool> {
+        let entropy = Entropy::<{ N }>::generate();
+        let mnemonic = self.from_entropy(&entropy.0)?;
+        let sentence = Zeroizing::new(mnemonic.to_string());
+
+        // The checksum of the words must be valid
+        if !self.validate_mnemonic(&sentence)? {
+            return Ok(false);
+        }
+
+        // entropy -> mnemonic -> entropy must give back our bytes
+        let recovered_entropy = Zeroizing::new(self.mnemonic_to_entropy(&sentence)?);
+        if recovered_entropy.as_slice() != entropy.0.as_slice() {
+            return Ok(false);
+        }
+
+        // Recovering must give the seed we derive when generating,
+        // with and without a passphrase
+        for passphrase in [None, Some("self test")] {
+            let seed = Zeroizing::new(Bip39Generator::seed_array_with_iterations(
+                &sentence,
+                passphrase,
+                self.iterations(),
+            )?);
+            let recovered = self.recover(&sentence, passphrase)?;
+
+            if !recovered.ct_eq(&Seed::new(*seed)) {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    // This method builds a mnemonic from dice rolls instead of
+    // our RNG, for anyone who would rather trust physical dice.
+    // Just like Coldcard we write the rolls out as digits and
+    // hash them with SHA256, which spreads the entropy of the
+    // rolls evenly over the bits, and keep the first `bits` bits.
+    // A d6 roll carries log2(6) ~= 2.58 bits so we require enough
+    // rolls to cover `bits` rounded to the nearest roll, which
+    // gives the same 50 rolls for 128 bits and 99 rolls for
+    // 256 bits that Coldcard asks for.
+    pub fn from_dice(&mut self, rolls: &[u8], bits: usize) -> io::Result<Mnemonic> {
+        // We can only produce entropy sizes BIP39 allows
+        Bip39Generator::check_entropy_bits(bits)?;
+
+        // Every roll must be a face of a six sided die
+        if let Some(roll) = rolls.iter().find(|roll| !(1..=6).contains(*roll)) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid dice roll {}, expected 1 to 6", roll),
+            ));
+        }
+
+        let rolls_needed = (bits as f64 / 6f64.log2()).round() as usize;
+        if rolls.len() < rolls_needed {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "{} dice rolls only cover {} bits, at least {} rolls are needed for {} bits",
+                    rolls.len(),
+                    (rolls.len() as f64 * 6f64.log2()) as usize,
+                    rolls_needed,
+                    bits
+                ),
+            ));
+        }
+
+        // Write each roll as its digit and hash them all
+        let digits = Zeroizing::new(rolls.iter().map(|roll| b'0' + roll).collect::<Vec<u8>>());
+        let mut hash = Sha256::digest(digits.as_slice());
+
+        // Keep only as many bytes as we asked for
+        let mnemonic = self.from_entropy(&hash[..bits / 8]);
+        hash.zeroize();
+
+        mnemonic
+    }
+
+    // This method builds a mnemonic from coin flips written as
+    // a string of `0`s and `1`s, one bit per flip. The first
+    // `bits` flips are used as the entropy directly so this is
+    // a simple way to feed physical randomness into an
+    // air-gapped machine.
+    pub fn from_coin_flips(&mut self, flips: &str, bits: usize) -> io::Result<Mnemonic> {
+        // We can only produce entropy sizes BIP39 allows
+        Bip39Generator::check_entropy_bits(bits)?;
+
+        // Every flip must be either `0` or `1`
+        if let Some(flip) = flips.chars().find(|flip| *flip != '0' && *flip != '1') {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid coin flip `{}`, expected `0` or `1`", flip),
+            ));
+        }
+
+        if flips.len() < bits {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "{} coin flips only cover {} bits, {} flips are needed",
+                    flips.len(),
+                    flips.len(),
+                    bits
+                ),
+            ));
+        }
+
+        // Pack every 8 flips into a byte, most significant bit first
+        let entropy = Zeroizing::new(
+            flips.as_bytes()[..bits]
+                .chunks(8)
+                .map(|byte| byte.iter().fold(0u8, |acc, &flip| (acc << 1) | (flip - b'0')))
+                .collect::<Vec<u8>>(),
+        );
+
+        self.from_entropy(&entropy)
+    }
+
+    // This method checks that `bits` is an entropy size BIP39
+    // allows, used when the entropy comes from the user
+    fn check_entropy_bits(bits: usize) -> io::Result<()> {
+        if !bits.is_multiple_of(8) || !ENTROPY_SIZES.contains(&(bits / 8)) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "{} bits is not a valid BIP39 entropy size, expected 128, 160, 192, 224 or 256",
+                    bits
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Same as `from_entropy()` but with the entropy written as
+    // hex, e.g. `"7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f"`, which is
+    // how some cold storage schemes keep it instead of words
+    pub fn from_entropy_hex(&mut self, hex: &str) -> io::Result<Mnemonic> {
+        // Decoding fails on an odd number of digits or anything
+        // that isn't a hex digit, the error tells us which
+        let entropy = Zeroizing::new(
+            hex::decode(hex.trim()).map_err(|error| Bip39Error::InvalidHex(error.to_string()))?,
+        );
+
+        self.from_entropy(&entropy)
+    }
+
+    // This method builds a mnemonic from entropy we already
+    // have instead of generating random bytes. This is what
+    // lets us check our output against the BIP39 test vectors
+    // which publish the entropy together with the expected words.
+    pub fn from_entropy(&mut self, entropy: &[u8]) -> io::Result<Mnemonic> {
+        // BIP39 only defines mnemonics for a handful of entropy
+        // sizes, anything else would give us a nonsense word count
+        if !ENTROPY_SIZES.contains(&entropy.len()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "{} bytes is not a valid BIP39 entropy size, expected one of {:?}",
+                    entropy.len(),
+                    ENTROPY_SIZES
+                ),
+            ));
+        }
+
+        // Start from a clean slate in case this generator
+        // already made a mnemonic before
+        self.reset();
+
+        // Next, let's generate our checksum
+        self.generate_checksum(entropy);
+  
+        // Next we compute the decimal numbers we will use
+        // to get our wordlist
+        self.compute(entropy.len());
+
+        // When streaming we only read the words we need, unless
+        // the wordlist has already been loaded anyway
+        if self.streaming && self.wordlist.get().is_none() {
+            let words = match &self.path {
+                Some(path) => self.stream_words(
+                    io::BufReader::new(File::open(path)?),
+                    &path.display().to_string(),
+                )?,
+                None => self.stream_words(
+                    self.language.wordlist().as_bytes(),
+                    &format!("{:?}", self.language),
+                )?,
+            };
+
+            return Ok(Mnemonic::new(
+                words,
+                self.mnemonic_index.to_vec(),
+                self.language,
+                self.bits_per_word(),
+            ));
+        }
+
+        // Load the wordlist into memory
+        let wordlist = self.load_wordlist()?;
+
+        // Create a Vector to hold the words of our mnemonic
+        let mut words = Vec::<String>::with_capacity(self.mnemonic_index.len());
+
+        // Iterate through the decimal numbers
+        // and for each decimal number get the word
+        // in it's index in the wordlist (wordlist[index from decimal number]
+        for line_number in self.mnemonic_index.iter() {
+            // Convert our decimal index (line_numer) to 
+            // a usize since Rust is very strict in that
+            // you can only index an array using a usize
+            // so we dereference and cast using `as usize`
+            let word = &wordlist[*line_number as usize];
+
+            // Keep the word for our mnemonic
+            words.push(word.clone());
+        }
+
+        // Return the mnemonic, the `Mnemonic` type takes care
+        // of joining the words and wiping them when dropped
+        Ok(Mnemonic::new(
+            words,
+            self.mnemonic_index.to_vec(),
+            self.language,
+            self.bits_per_word(),
+        ))
+    }
+
+    // This method reads the words at our `mnemonic_index` out
+    // of `reader` in a single pass. We visit the positions of
+    // our words in the order of their indexes so each line
+    // we read is compared against the next index we need only.
+    // The whole wordlist is still read to the end so it gets the
+    // same checks as `check_wordlist()` without being kept.
+    fn stream_words(&self, mut reader: impl BufRead, source: &str) -> io::Result<Vec<String>> {
+        let indices = &self.mnemonic_index;
+
+        // The positions of our words, sorted by their index
+        let mut order = Zeroizing::new((0..indices.len()).collect::<Vec<usize>>());
+        order.sort_unstable_by_key(|&position| indices[position]);
+
+        let mut words = vec![String::new(); indices.len()];
+        let mut next = 0;
+        let mut line = Zeroizing::new(String::new());
+        let mut line_number = 0;
+        // The first empty line we read, which is only fine if
+        // nothing but empty lines follow it
+        let mut empty_line = None;
+
+        while reader.read_line(&mut line)? > 0 {
+            // Same as `check_wordlist()`, a byte order mark in
+            // front of the first word is not part of it
+            let text = match line_number {
+                0 => line.trim_start_matches(BYTE_ORDER_MARK),
+                _ => line.as_str(),
+            };
+            let word = text.trim();
+
+            if word.is_empty() {
+                empty_line.get_or_insert(line_number + 1);
+                line.clear();
+                continue;
+            }
+
+            // Same as `check_wordlist()`, an empty line would
+            // shift every word after it to the wrong index
+            if let Some(empty_line) = empty_line {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid wordlist {}: line {} is empty", source, empty_line),
+                ));
+            }
+
+            // The same word can appear several times in a mnemonic
+            while next < order.len() && indices[order[next]] as usize == line_number {
+                words[order[next]] = word.to_owned();
+                next += 1;
+            }
+
+            line_number += 1;
+            line.clear();
+        }
+
+        if line_number != self.wordlist_length() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "invalid wordlist {}: expected {} words but found {}",
+                    source,
+                    self.wordlist_length(),
+                    line_number
+                ),
+            ));
+        }
+
+        Ok(words)
+    }
+
+    // This method returns the 11 bit wordlist indexes behind the
+    // words we generated, handy for comparing our output with
+    // other implementations index by index. They belong to the
+    // last mnemonic we generated, every generation starts afresh.
+    pub fn indices(&self) -> &[u16] {
+        &self.mnemonic_index
+    }
+
+    // This method returns every word of our wordlist that
+    // starts with `prefix`, which is what a recovery UI needs
+    // to suggest words as the user types. If the wordlist
+    // can't be loaded there is nothing to suggest.
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<&str> {
+        match self.load_wordlist() {
+            Ok(wordlist) => wordlist
+                .iter()
+                .filter(|word| word.starts_with(prefix))
+                .map(String::as_str)
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    // BIP39 wordlists are chosen so the first four letters of
+    // a word are enough to identify it. This method resolves
+    // such a prefix (or a shorter word typed out in full, like
+    // `add`) to the full word, returning `None` when the prefix
+    // is too short or doesn't match exactly one word.
+    pub fn word_from_prefix(&self, prefix: &str) -> Option<&str> {
+        let candidates = self.words_with_prefix(prefix);
+
+        // A word typed out in full always wins, even when it is
+        // the prefix of longer words too
+        if let Some(word) = candidates.iter().find(|word| **word == prefix) {
+            return Some(word);
+        }
+
+        match candidates.as_slice() {
+            [word] if prefix.chars().count() >= 4 => Some(word),
+            _ => None,
+        }
+    }
+
+    // Backups often have a single mistyped word. For every
+    // word of the mnemonic that is not in our wordlist this
+    // method returns its zero based position together with the
+    // words that are one edit (insertion, deletion or
+    // substitution) away from it, so a recovery UI can ask
+    // "did you mean ...?". Combined with the checksum it is
+    // usually clear which candidate is right.
+    pub fn suggest_corrections(&self, mnemonic: &str) -> Vec<(usize, Vec<String>)> {
+        let wordlist = match self.load_wordlist() {
+            Ok(wordlist) => wordlist,
+            Err(_) => return Vec::new(),
+        };
+
+        self.find_invalid_words(mnemonic)
+            .into_iter()
+            .map(|(position, word)| {
+                let candidates = wordlist
+                    .iter()
+                    .filter(|candidate| levenshtein(&word, candidate) <= 1)
+                    .cloned()
+                    .collect();
+
+                (position, candidates)
+            })
+            .collect()
+    }
+
+    // This method returns the zero based position and the word
+    // itself for every word of the mnemonic that is not in our
+    // wordlist, so a UI can highlight exactly which words to fix.
+    // It doesn't look at the word count or checksum. If the
+    // wordlist can't be loaded there is nothing to compare
+    // against so we return no words.
+    pub fn find_invalid_words(&self, mnemonic: &str) -> Vec<(usize, String)> {
+        let word_index = match self.load_word_index() {
+            Ok(word_index) => word_index,
+            Err(_) => return Vec::new(),
+        };
+
+        mnemonic
+            .split_whitespace()
+            .enumerate()
+            .filter(|(_, word)| {
+                self.resolve_word(word)
+                    .map_or(true, |word| !word_index.contains_key(word.as_str()))
+            })
+            .map(|(position, word)| (position, word.to_owned()))
+            .collect()
+    }
+
+    // This loads the map from the `accent_key()` of each word
+    // to its index, the same way `load_word_index()` does
+    fn load_accent_index(&self) -> io::Result<&HashMap<String, u16>> {
+        if let Some(accent_index) = self.accent_index.get() {
+            return Ok(accent_index);
+        }
+
+        let accent_index = self
+            .load_wordlist()?
+            .iter()
+            .enumerate()
+            .map(|(index, word)| (accent_key(word).to_string(), index as u16))
+            .collect::<HashMap<String, u16>>();
+
+        Ok(self.accent_index.get_or_init(|| accent_index))
+    }
+
+    // This method returns the word of our wordlist that `word`
+    // stands for, in NFKD form like the keys of our word index.
+    // Without accent tolerant matching, or for English, that is
+    // always `word` itself. When nothing in the wordlist matches
+    // we also return `word` so the lookup that follows reports
+    // it as unknown.
+    fn resolve_word(&self, word: &str) -> io::Result<Zeroizing<String>> {
+        if !self.accent_tolerant
+            || !self.language.is_latin()
+            || self.language == Language::English
+        {
+            return Ok(nfkd_word(word));
+        }
+
+        match self.load_accent_index()?.get(accent_key(word).as_str()) {
+            Some(&index) => Ok(nfkd_word(&self.load_wordlist()?[index as usize])),
+            None => Ok(nfkd_word(word)),
+        }
+    }
+
+    // This method rewrites every word of the mnemonic with
+    // `resolve_word()`, joined by the separator of our language
+    fn resolve_words(&self, mnemonic: &str) -> io::Result<Zeroizing<String>> {
+        let mut resolved = Zeroizing::new(String::with_capacity(mnemonic.len() * 2));
+
+        for (index, word) in mnemonic.split_whitespace().enumerate() {
+            if index > 0 {
+                resolved.push_str(self.language.separator());
+            }
+            resolved.push_str(&self.resolve_word(word)?);
+        }
+
+        Ok(resolved)
+    }
+
+    // This method checks that a mnemonic is well formed by
+    // turning the words back into entropy which verifies
+    // the checksum along the way. Any problem with the
+    // mnemonic itself gives us `false` while failing to
+    // load the wordlist is still reported as an error.
+    // The words are looked up in the wordlist this generator was
+    // created with, the file of `new()`, the words of
+    // `from_wordlist()` or the embedded list of `with_language()`,
+    // so a mnemonic can be checked strictly against a third-party
+    // wordlist. `mnemonic_to_entropy()` works the same way.
+    pub fn validate_mnemonic(&self, mnemonic: &str) -> io::Result<bool> {
+        match self.mnemonic_to_entropy(mnemonic) {
+            Ok(_) => Ok(true),
+            Err(error) if error.kind() == io::ErrorKind::InvalidInput => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    // This method is the inverse of `generate_checksum()` and
+    // `compute()`. It turns the words back into bits, splits
+    // off the checksum bits, compares them against a freshly
+    // computed SHA256 checksum and returns the original entropy.
+    pub fn mnemonic_to_entropy(&self, mnemonic: &str) -> io::Result<Vec<u8>> {
+        // Load the word -> index map into memory
+        let word_index = self.load_word_index()?;
+        let mnemonic = self.resolve_words(mnemonic)?;
+
+        Ok(entropy_from_words_with(
+            word_index,
+            &mnemonic,
+            self.bits_per_word(),
+        )?)
+    }
+
+    // This method shows where the checksum ends up: the last word
+    // of the mnemonic of `entropy`, which must be `N` bytes. Its 11
+    // bits are the last 7, 6, 5, 4 or 3 bits of the entropy followed
+    // by the 4, 5, 6, 7 or 8 checksum bits, the first bits of the
+    // SHA256 hash of the entropy. E.g. for 16 zero bytes the last 7
+    // bits are zero and the checksum is 0011, which gives index 3,
+    // "about" in the English wordlist.
+    pub fn checksum_word<const N: usize>(&self, entropy: &[u8]) -> io::Result<String> {
+        if entropy.len() != N {
+            return Err(Bip39Error::InvalidEntropyLength(entropy.len()).into());
+        }
+
+        // The indexes of the whole mnemonic come straight out of
+        // the entropy and checksum bits, the last one is the word
+        // holding the checksum
+        let indices = Zeroizing::new(crypto::entropy_to_indices_with(
+            entropy,
+            self.bits_per_word(),
+        )?);
+        let index = *indices.last().expect("a mnemonic always has words");
+
+        Ok(self.load_wordlist()?[index as usize].clone())
+    }
+
+    // This method helps when the last word of a mnemonic is lost.
+    // We take the other 11, 14, 17, 20 or 23 words and try every
+    // word of the wordlist in the last position, returning those
+    // that give a valid checksum in wordlist order. The last word
+    // holds 7, 6, 5, 4 or 3 bits of entropy next to the checksum
+    // so we get 128, 64, 32, 16 or 8 candidates, one of which is
+    // the lost word. The right one can then be found by checking
+    // which seed holds the funds.
+    pub fn complete_last_word(&self, partial: &str) -> io::Result<Vec<String>> {
+        let wordlist = self.load_wordlist()?;
+        let word_index = self.load_word_index()?;
+
+        let mut indices = Zeroizing::new(Vec::<u16>::new());
+        for word in partial.split_whitespace() {
+            let word = self.resolve_word(word)?;
+            match word_index.get(word.as_str()) {
+                Some(&index) => indices.push(index),
+                None => return Err(Bip39Error::UnknownWord(word.to_string()).into()),
+            }
+        }
+
+        // One word short of a mnemonic BIP39 defines
+        let bits_per_word = self.bits_per_word();
+        let counts = ENTROPY_SIZES.map(|n| word_count_with(n, bits_per_word) - 1);
+        if !counts.contains(&indices.len()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "expected {:?} words but found {}",
+                    counts,
+                    indices.len()
+                ),
+            ));
+        }
+
+        let mut candidates = Vec::new();
+        indices.push(0);
+        for (candidate, word) in wordlist.iter().enumerate() {
+            *indices.last_mut().expect("we just pushed the last word") = candidate as u16;
+
+            // The entropy itself is wiped straight away, we only
+            // want to know whether the checksum is valid
+            if crypto::indices_to_entropy_with(&indices, bits_per_word)
+                .map(Zeroizing::new)
+                .is_ok()
+            {
+                candidates.push(word.clone());
+            }
+        }
+
+        Ok(candidates)
+    }
+
+    // This method helps when the words of a backup were written
+    // down in the wrong order. We take the right words and look
+    // for an order whose checksum is valid, trying the order we
+    // were given first, then every swap of two neighbouring words
+    // (the most common mistake) and then every swap of any two
+    // words. We return the first order that validates, or `None`
+    // when none does, a word is unknown or the count is wrong.
+    //
+    // The search is bounded on purpose. There are n! orders of n
+    // words, about 479 million for 12 words and 6.2 * 10^23 for
+    // 24, far too many to try, while the swaps are at most 66 and
+    // 276 orders. Words that are more than two places off are
+    // not found. The checksum is also only 4 to 8 bits, so about
+    // 1 in 16 (12 words) to 1 in 256 (24 words) of the orders we
+    // try validates by chance. The order we return is therefore a
+    // candidate, not proof: always check that its seed gives the
+    // wallet you expect, e.g. by its fingerprint or first address.
+    pub fn is_permutation_valid(&self, words: &[String]) -> Option<Vec<String>> {
+        let word_index = self.load_word_index().ok()?;
+        let bits_per_word = self.bits_per_word();
+
+        let mut indices = Zeroizing::new(Vec::<u16>::with_capacity(words.len()));
+        for word in words {
+            let word = self.resolve_word(word).ok()?;
+            indices.push(*word_index.get(word.as_str())?);
+        }
+
+        if !ENTROPY_SIZES.iter().any(|&n| word_count_with(n, bits_per_word) == indices.len()) {
+            return None;
+        }
+
+        // The entropy itself is wiped straight away, we only
+        // want to know whether the checksum is valid
+        let valid = |indices: &[u16]| {
+            crypto::indices_to_entropy_with(indices, bits_per_word)
+                .map(Zeroizing::new)
+                .is_ok()
+        };
+
+        if valid(&indices) {
+            return Some(words.to_vec());
+        }
+
+        let count = indices.len();
+        let neighbours = (1..count).map(|j| (j - 1, j));
+        let others = (0..count).flat_map(|i| (i + 2..count).map(move |j| (i, j)));
+
+        for (i, j) in neighbours.chain(others) {
+            indices.swap(i, j);
+            if valid(&indices) {
+                let mut reordered = words.to_vec();
+                reordered.swap(i, j);
+                return Some(reordered);
+            }
+            indices.swap(i, j);
+        }
+
+        None
+    }
+
+    // This method will recover a seed from a mnemonic that 
+    // is protected using a passphrase. We pass in the
+    // mnemonic as passphrase arguments respectively as method
+   pub fn recover_secure(&self, mnemonic: &str, passphrase: &str) -> io::Result<Seed> {
+    // Call the `recover()` mnemonic using our passphrase
+    self.recover(mnemonic, Option::Some(passphrase))
+    }
+
+    // This method will recover a seed from a mnemonic that 
+    // is not protected using a passphrase
+    pub fn recover_insecure(&self, mnemonic: &str) -> io::Result<Seed> {
+    // Call the `recover()` mnemonic passing `Option::None` 
+    // for our passphrase
+    self.recover(mnemonic, Option::None)
+    }
+
+    // We recreate our seed phrase by passing our
+    // mnemonic as passphrase to the `seed()` method
+    // of the `Bip39Generator` just the same
+    // way we did when generating it.
+    // Before deriving anything we validate the mnemonic
+    // so that a typo is reported instead of silently
+    // producing a seed for the wrong wallet.
+    pub fn recover(&self, mnemonic: &str, passphrase: Option<&str>) -> io::Result<Seed> {
+        // Clean up what the user typed or pasted before looking
+        // up any words, the NFKD normalization BIP39 requires
+        // still happens separately inside `seed()`
+        let mnemonic = Bip39Generator::normalize_input(mnemonic, self.language);
+        // With accent tolerant matching the seed has to come from
+        // the words as the wordlist spells them
+        let mnemonic = self.resolve_words(&mnemonic)?;
+        let mnemonic = mnemonic.as_str();
+
+        // The error tells the user every problem we found
+        // with the mnemonic, e.g. each unknown word
+        Zeroizing::new(self.mnemonic_to_entropy(mnemonic)?);
+
+        let seed = Zeroizing::new(Bip39Generator::seed_with_iterations(
+            mnemonic,
+            passphrase,
+            self.iterations(),
+        )?);
+
+        Ok(Seed::from_bytes(&seed)?)
+    }
+
+    // Same as `recover()` but reads the passphrase out of the
+    // environment variable `variable`, which keeps it off the
+    // command line where other users could see it in the list
+    // of running processes. Our copy is wiped once the seed
+    // is derived.
+    pub fn recover_from_env(&self, mnemonic: &str, variable: &str) -> io::Result<Seed> {
+        let passphrase = passphrase_from_env(variable)?;
+
+        self.recover(mnemonic, Some(passphrase.as_str()))
+    }
+
+    // This function tidies up a mnemonic typed or pasted by a
+    // user, e.g. `"  Abandon   Ability\tAble "`. It trims the
+    // ends, collapses runs of whitespace into a single separator
+    // and lowercases the words. Lowercasing does nothing for
+    // scripts without case such as Japanese or Chinese, so it is
+    // safe for every language.
+    fn normalize_input(mnemonic: &str, language: Language) -> Zeroizing<String> {
+        let mut normalized = Zeroizing::new(String::with_capacity(mnemonic.len() * 2));
+
+        for (index, word) in mnemonic.split_whitespace().enumerate() {
+            if index > 0 {
+                normalized.push_str(language.separator());
+            }
+            normalized.extend(word.chars().flat_map(char::to_lowercase));
+        }
+
+        normalized
+    }
+        
+
+}
+
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    // The English test vectors published by Trezor for BIP39 as
+    // (entropy, mnemonic, seed) where the seed uses the passphrase "TREZOR"
+    const TREZOR_VECTORS: [(&str, &str, &str); 24] = [
+        (
+            "00000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+        ),
+        (
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+        ),
+        (
+            "80808080808080808080808080808080",
+            "letter advice cage absurd amount doctor acoustic avoid letter advice cage above",
+            "d71de856f81a8acc65e6fc851a38d4d7ec216fd0796d0a6827a3ad6ed5511a30fa280f12eb2e47ed2ac03b5c462a0358d18d69fe4f985ec81778c1b370b652a8",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffff",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+            "ac27495480225222079d7be181583751e86f571027b0497b5b5d11218e0a8a13332572917f0f8e5a589620c6f15b11c61dee327651a14c34e18231052e48c069",
+        ),
+        (
+            "000000000000000000000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon agent",
+            "035895f2f481b1b0f01fcf8c289c794660b289981a78f8106447707fdd9666ca06da5a9a565181599b79f53b844d8a71dd9f439c52a3d7b3e8a79c906ac845fa",
+        ),
+        (
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal will",
+            "f2b94508732bcbacbcc020faefecfc89feafa6649a5491b8c952cede496c214a0c7b3c392d168748f2d4a612bada0753b52a1c7ac53c1e93abd5c6320b9e95dd",
+        ),
+        (
+            "808080808080808080808080808080808080808080808080",
+            "letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter always",
+            "107d7c02a5aa6f38c58083ff74f04c607c2d2c0ecc55501dadd72d025b751bc27fe913ffb796f841c49b1d33b610cf0e91d3aa239027f5e99fe4ce9e5088cd65",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffff",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo when",
+            "0cd6e5d827bb62eb8fc1e262254223817fd068a74b5b449cc2f667c3f1f985a76379b43348d952e2265b4cd129090758b3e3c2c49103b5051aac2eaeb890a528",
+        ),
+        (
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art",
+            "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8",
+        ),
+        (
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth title",
+            "bc09fca1804f7e69da93c2f2028eb238c227f2e9dda30cd63699232578480a4021b146ad717fbb7e451ce9eb835f43620bf5c514db0f8add49f5d121449d3e87",
+        ),
+        (
+            "8080808080808080808080808080808080808080808080808080808080808080",
+            "letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic bless",
+            "c0c519bd0e91a2ed54357d9d1ebef6f5af218a153624cf4f2da911a0ed8f7a09e2ef61af0aca007096df430022f7a2b6fb91661a9589097069720d015e4e982f",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+            "dd48c104698c30cfe2b6142103248622fb7bb0ff692eebb00089b32d22484e1613912f0a5b694407be899ffd31ed3992c456cdf60f5d4564b8ba3f05a69890ad",
+        ),
+        (
+            "9e885d952ad362caeb4efe34a8e91bd2",
+            "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic",
+            "274ddc525802f7c828d8ef7ddbcdc5304e87ac3535913611fbbfa986d0c9e5476c91689f9c8a54fd55bd38606aa6a8595ad213d4c9c9f9aca3fb217069a41028",
+        ),
+        (
+            "6610b25967cdcca9d59875f5cb50b0ea75433311869e930b",
+            "gravity machine north sort system female filter attitude volume fold club stay feature office ecology stable narrow fog",
+            "628c3827a8823298ee685db84f55caa34b5cc195a778e52d45f59bcf75aba68e4d7590e101dc414bc1bbd5737666fbbef35d1f1903953b66624f910feef245ac",
+        ),
+        (
+            "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+            "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict delay length",
+            "64c87cde7e12ecf6704ab95bb1408bef047c22db4cc7491c4271d170a1b213d20b385bc1588d9c7b38f1b39d415665b8a9030c9ec653d75e65f847d8fc1fc440",
+        ),
+        (
+            "c0ba5a8e914111210f2bd131f3d5e08d",
+            "scheme spot photo card baby mountain device kick cradle pact join borrow",
+            "ea725895aaae8d4c1cf682c1bfd2d358d52ed9f0f0591131b559e2724bb234fca05aa9c02c57407e04ee9dc3b454aa63fbff483a8b11de949624b9f1831a9612",
+        ),
+        (
+            "6d9be1ee6ebd27a258115aad99b7317b9c8d28b6d76431c3",
+            "horn tenant knee talent sponsor spell gate clip pulse soap slush warm silver nephew swap uncle crack brave",
+            "fd579828af3da1d32544ce4db5c73d53fc8acc4ddb1e3b251a31179cdb71e853c56d2fcb11aed39898ce6c34b10b5382772db8796e52837b54468aeb312cfc3d",
+        ),
+        (
+            "9f6a2878b2520799a44ef18bc7df394e7061a224d2c33cd015b157d746869863",
+            "panda eyebrow bullet gorilla call smoke muffin taste mesh discover soft ostrich alcohol speed nation flash devote level hobby quick inner drive ghost inside",
+            "72be8e052fc4919d2adf28d5306b5474b0069df35b02303de8c1729c9538dbb6fc2d731d5f832193cd9fb6aeecbc469594a70e3dd50811b5067f3b88b28c3e8d",
+        ),
+        (
+            "23db8160a31d3e0dca3688ed941adbf3",
+            "cat swing flag economy stadium alone churn speed unique patch report train",
+            "deb5f45449e615feff5640f2e49f933ff51895de3b4381832b3139941c57b59205a42480c52175b6efcffaa58a2503887c1e8b363a707256bdd2b587b46541f5",
+        ),
+        (
+            "8197a4a47f0425faeaa69deebc05ca29c0a5b5cc76ceacc0",
+            "light rule cinnamon wrap drastic word pride squirrel upgrade then income fatal apart sustain crack supply proud access",
+            "4cbdff1ca2db800fd61cae72a57475fdc6bab03e441fd63f96dabd1f183ef5b782925f00105f318309a7e9c3ea6967c7801e46c8a58082674c860a37b93eda02",
+        ),
+        (
+            "066dca1a2bb7e8a1db2832148ce9933eea0f3ac9548d793112d9a95c9407efad",
+            "all hour make first leader extend hole alien behind guard gospel lava path output census museum junior mass reopen famous sing advance salt reform",
+            "26e975ec644423f4a4c4f4215ef09b4bd7ef924e85d1d17c4cf3f136c2863cf6df0a475045652c57eb5fb41513ca2a2d67722b77e954b4b3fc11f7590449191d",
+        ),
+        (
+            "f30f8c1da665478f49b001d94c5fc452",
+            "vessel ladder alter error federal sibling chat ability sun glass valve picture",
+            "2aaa9242daafcee6aa9d7269f17d4efe271e1b9a529178d7dc139cd18747090bf9d60295d0ce74309a78852a9caadf0af48aae1c6253839624076224374bc63f",
+        ),
+        (
+            "c10ec20dc3cd9f652c7fac2f1230f7a3c828389a14392f05",
+            "scissors invite lock maple supreme raw rapid void congress muscle digital elegant little brisk hair mango congress clump",
+            "7b4a10be9d98e6cba265566db7f136718e1398c71cb581e1b2f464cac1ceedf4f3e274dc270003c670ad8d02c4558b2f8e39edea2775c9e232c7cb798b069e88",
+        ),
+        (
+            "f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f",
+            "void come effort suffer camp survey warrior heavy shoot primary clutch crush open amazing screen patrol group space point ten exist slush involve unfold",
+            "01f5bced59dec48e362f2c45b5de68b9fd6c92c6634f44d6d40aab69056506f0e35524a518034ddc1192e1dacd32c1ed3eaa3c3b131c88ed8e7e54c49a5d0998",
+        ),
+    ];
+
+    #[test]
+    fn seed_normalizes_passphrase_to_nfkd() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        // The NFC (composed) form of the passphrase must give
+        // the same seed as the NFKD (decomposed) form
+        let composed = Bip39Generator::seed(mnemonic, Some("ПÁSSwörd")).unwrap();
+        let decomposed =
+            Bip39Generator::seed(mnemonic, Some("ПA\u{301}SSwo\u{308}rd")).unwrap();
+
+        assert_eq!(composed, decomposed);
+        assert_eq!(
+            hex::encode(&composed),
+            "9e7f709427655fddfbb1331acad4383ae5254238fe6e3b3cfb2d5f1da9974e3c6b916a00d01eef27c3ca0a34ae687e3ef90f61d54b06b1dcb7e9040731ea2c21"
+        );
+    }
+
+    #[test]
+    fn trezor_english_vectors() {
+        for (entropy, expected_mnemonic, expected_seed) in TREZOR_VECTORS {
+            let mut generator = Bip39Generator::with_language(Language::English);
+            let entropy = hex::decode(entropy).unwrap();
+
+            let mnemonic = generator.from_entropy(&entropy).unwrap();
+            assert_eq!(mnemonic.to_string(), expected_mnemonic);
+
+            let seed = Bip39Generator::seed(expected_mnemonic, Some("TREZOR")).unwrap();
+            assert_eq!(hex::encode(seed), expected_seed);
+
+            // Going back from the words must give us the same entropy
+            assert_eq!(generator.mnemonic_to_entropy(expected_mnemonic).unwrap(), entropy);
+        }
+    }
+
+    #[test]
+    fn empty_passphrase_is_the_same_as_no_passphrase() {
+        let (_, mnemonic, _) = TREZOR_VECTORS[0];
+
+        assert_eq!(
+            Bip39Generator::seed(mnemonic, None).unwrap(),
+            Bip39Generator::seed(mnemonic, Some("")).unwrap()
+        );
+
+        let generator = Bip39Generator::with_language(Language::English);
+        assert_eq!(
+            generator.recover(mnemonic, None).unwrap().as_bytes(),
+            generator.recover(mnemonic, Some("")).unwrap().as_bytes()
+        );
+    }
+
+    #[test]
+    fn seed_with_prefix_defaults_to_bip39() {
+        let (_, mnemonic, expected_seed) = TREZOR_VECTORS[0];
+
+        let seed = Bip39Generator::seed_with_prefix(mnemonic, Some("TREZOR"), SALT_PREFIX).unwrap();
+        assert_eq!(hex::encode(seed), expected_seed);
+
+        let other = Bip39Generator::seed_with_prefix(mnemonic, Some("TREZOR"), "electrum").unwrap();
+        assert_ne!(hex::encode(other), expected_seed);
+    }
+
+    #[test]
+    fn verify_seed_checks_mnemonic_and_passphrase() {
+        let (_, mnemonic, expected_seed) = TREZOR_VECTORS[0];
+        let expected = Seed::from_hex(expected_seed).unwrap();
+
+        assert!(Bip39Generator::verify_seed(mnemonic, Some("TREZOR"), &expected).unwrap());
+        assert!(!Bip39Generator::verify_seed(mnemonic, Some("trezor"), &expected).unwrap());
+        assert!(!Bip39Generator::verify_seed(mnemonic, None, &expected).unwrap());
+    }
+
+    #[test]
+    fn mnemonic_id_is_the_master_fingerprint() {
+        let (_, mnemonic, _) = TREZOR_VECTORS[0];
+        let mnemonic: Mnemonic = mnemonic.parse().unwrap();
+
+        assert_eq!(hex::encode(mnemonic.id()), "73c5da0a");
+    }
+
+    #[test]
+    fn debug_output_never_shows_secrets() {
+        let (_, mnemonic, _) = TREZOR_VECTORS[0];
+        let mnemonic: Mnemonic = mnemonic.parse().unwrap();
+        assert_eq!(format!("{:?}", mnemonic), "Mnemonic(<redacted 12 words>)");
+
+        let seed = Seed::from(&mnemonic);
+        assert_eq!(format!("{:?}", seed), "Seed(<redacted>)");
+
+        let entropy = Entropy::<16>::try_from(&mnemonic).unwrap();
+        assert_eq!(format!("{:?}", entropy), "Entropy(<redacted 16 bytes>)");
+
+        let root = bip32::ExtendedPrivKey::new_master(seed.as_bytes()).unwrap();
+        assert_eq!(
+            format!("{:?}", root),
+            "ExtendedPrivKey { depth: 0, parent_fingerprint: \"00000000\", child_number: 0, \
+             private_key: <redacted>, chain_code: <redacted> }"
+        );
+
+        let mut generator = Bip39Generator::with_language(Language::English);
+        let wallet = generator.generate_full::<16>(None).unwrap();
+        let wallet_debug = format!("{:?}", wallet);
+        assert!(!wallet_debug.contains(&hex::encode(&wallet.entropy)));
+        assert!(wallet_debug.contains("Mnemonic(<redacted 12 words>)"));
+        assert_eq!(
+            format!("{:?}", generator),
+            "Bip39Generator { path: None, language: English, accent_tolerant: false, \
+             network: Mainnet, iterations: 2048, streaming: false, bits_per_word: 11, .. }"
+        );
+    }
+
+    #[test]
+    fn reused_generator_starts_each_mnemonic_afresh() {
+        let mut generator = Bip39Generator::with_language(Language::English);
+
+        assert_eq!(generator.mnemonic::<32>().unwrap().word_count(), 24);
+        assert_eq!(generator.mnemonic::<16>().unwrap().word_count(), 12);
+        assert_eq!(generator.mnemonic::<24>().unwrap().word_count(), 18);
+
+        for (entropy, expected_mnemonic, _) in TREZOR_VECTORS {
+            let entropy = hex::decode(entropy).unwrap();
+            let mnemonic = generator.from_entropy(&entropy).unwrap();
+            assert_eq!(mnemonic.to_string(), expected_mnemonic);
+        }
+    }
+
+    #[test]
+    fn mnemonic_words_are_the_words_of_the_wordlist() {
+        for language in [Language::English, Language::Japanese] {
+            let mut generator = Bip39Generator::with_language(language);
+            let words = generator.mnemonic_words::<20>().unwrap();
+            assert_eq!(words.len(), 15);
+
+            let wordlist = language.wordlist();
+            assert!(words.iter().all(|word| wordlist.lines().any(|entry| entry == word)));
+
+            let sentence = words.join(language.separator());
+            assert!(sentence.parse::<Mnemonic>().is_ok());
+        }
+    }
+
+    #[test]
+    fn wordlist_files_tolerate_editor_artifacts() {
+        // "abandon ... about" starts with the first word of the list
+        let (entropy, expected_mnemonic, _) = TREZOR_VECTORS[0];
+        let entropy = hex::decode(entropy).unwrap();
+        let words = Language::English.wordlist().lines().collect::<Vec<&str>>();
+        let directory = std::env::temp_dir();
+
+        let cases = [
+            ("bom", format!("\u{FEFF}{}\n", words.join("\n")), true),
+            ("trailing", format!("{}\r\n\r\n\n", words.join("\r\n")), true),
+            (
+                "middle",
+                format!("{}\n\n{}\n", words[..5].join("\n"), words[5..].join("\n")),
+                false,
+            ),
+        ];
+
+        for (name, contents, valid) in cases {
+            let file_name = format!("wallet-bk-recovery-{}-{}.txt", name, std::process::id());
+            let path = directory.join(file_name);
+            std::fs::write(&path, contents).unwrap();
+
+            for streaming in [false, true] {
+                let mut generator = Bip39Generator::builder()
+                    .wordlist_path(&path)
+                    .streaming(streaming)
+                    .build();
+
+                match generator.from_entropy(&entropy) {
+                    Ok(mnemonic) => {
+                        assert!(valid, "{} should be rejected", name);
+                        assert_eq!(mnemonic.to_string(), expected_mnemonic);
+                    }
+                    Err(error) => {
+                        assert!(!valid, "{} should load: {}", name, error);
+                        assert!(error.to_string().contains("line 6 is empty"), "{}", error);
+                    }
+                }
+            }
+
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+
+    #[test]
+    fn seeded_rng_mnemonics_are_reproducible() {
+        let mut generator = Bip39Generator::with_language(Language::English);
+        let first = generator.from_seeded_rng::<16>(42).unwrap();
+
+        assert_eq!(first, generator.from_seeded_rng::<16>(42).unwrap());
+        assert_ne!(first, generator.from_seeded_rng::<16>(43).unwrap());
+        // Tests elsewhere may rely on these exact words, changing
+        // how the entropy is drawn from the RNG would break them
+        assert_eq!(
+            first.to_string(),
+            "joke east into casino sister dove grain glass physical soft dog square"
+        );
+    }
+
+    #[test]
+    fn seed_params_follow_the_iteration_count() {
+        let generator = Bip39Generator::with_language(Language::English);
+        assert_eq!(generator.seed_params(), SeedParams::BIP39);
+        assert_eq!(generator.seed_params().to_string(), "2048 rounds of HMAC-SHA512");
+
+        let generator = Bip39Generator::builder().iterations(100_000).build();
+        assert_eq!(generator.seed_params().to_string(), "100000 rounds of HMAC-SHA512");
+    }
+
+    #[test]
+    fn validation_uses_the_wordlist_of_the_generator() {
+        let english = Bip39Generator::with_language(Language::English);
+        let words = Language::English.wordlist().lines().map(str::to_owned);
+        let (entropy, _, _) = TREZOR_VECTORS[3];
+        let entropy = hex::decode(entropy).unwrap();
+
+        // The English words in reverse order, every word is still
+        // English but at another index
+        let reversed = words.clone().rev().collect::<Vec<String>>();
+        let mut in_memory = Bip39Generator::from_wordlist(reversed).unwrap();
+        let mnemonic = in_memory.from_entropy(&entropy).unwrap().to_string();
+        assert!(in_memory.validate_mnemonic(&mnemonic).unwrap());
+        assert_eq!(in_memory.mnemonic_to_entropy(&mnemonic).unwrap(), entropy);
+        assert!(!english.validate_mnemonic(&mnemonic).unwrap());
+
+        // A file of words that aren't English at all
+        let file_name = format!("wallet-bk-recovery-custom-{}.txt", std::process::id());
+        let path = std::env::temp_dir().join(file_name);
+        let custom = words.map(|word| format!("x{}", word)).collect::<Vec<String>>();
+        std::fs::write(&path, custom.join("\n")).unwrap();
+
+        let mut from_file = Bip39Generator::new(&path);
+        let mnemonic = from_file.from_entropy(&entropy).unwrap().to_string();
+        assert!(from_file.validate_mnemonic(&mnemonic).unwrap());
+        assert_eq!(from_file.mnemonic_to_entropy(&mnemonic).unwrap(), entropy);
+        assert!(!english.validate_mnemonic(&mnemonic).unwrap());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn mnemonic_compares_with_sentences() {
+        let (_, sentence, _) = TREZOR_VECTORS[0];
+        let mnemonic: Mnemonic = sentence.parse().unwrap();
+
+        assert_eq!(mnemonic, sentence);
+        assert!(mnemonic == *sentence);
+        assert_eq!(mnemonic, format!("  {}\n", sentence.replace(' ', " \t ")).as_str());
+        assert_ne!(mnemonic, sentence.trim_end_matches(" about"));
+        assert_ne!(mnemonic, format!("{} about", sentence).as_str());
+        assert_ne!(mnemonic, "");
+
+        // The Spanish wordlist spells "ábaco" in NFKD form
+        let mut generator = Bip39Generator::with_language(Language::Spanish);
+        let spanish = generator.from_entropy(&[0u8; 16]).unwrap();
+        assert_eq!(spanish, "\u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \
+                            \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco \u{e1}baco abierto");
+    }
+
+    #[test]
+    fn permutation_search_finds_a_valid_order() {
+        let generator = Bip39Generator::with_language(Language::English);
+
+        for (_, mnemonic, _) in TREZOR_VECTORS {
+            let words = mnemonic.split(' ').map(str::to_owned).collect::<Vec<String>>();
+            assert_eq!(generator.is_permutation_valid(&words), Some(words.clone()));
+
+            // Whatever order we get back must validate and hold
+            // exactly the words we wrote down
+            let mut swapped = words.clone();
+            swapped.swap(3, 7);
+            let found = generator.is_permutation_valid(&swapped).unwrap();
+            assert!(generator.validate_mnemonic(&found.join(" ")).unwrap());
+
+            let mut sorted = found.clone();
+            sorted.sort();
+            let mut expected = words.clone();
+            expected.sort();
+            assert_eq!(sorted, expected);
+        }
+
+        let mut words = vec!["abandon".to_owned(); 12];
+        words[5] = "notaword".to_owned();
+        assert_eq!(generator.is_permutation_valid(&words), None);
+        let words = vec!["abandon".to_owned(); 11];
+        assert_eq!(generator.is_permutation_valid(&words), None);
+    }
+
+    #[test]
+    fn checksum_word_is_the_last_word() {
+        let generator = Bip39Generator::with_language(Language::English);
+        assert_eq!(generator.checksum_word::<16>(&[0u8; 16]).unwrap(), "about");
+
+        for (entropy, mnemonic, _) in TREZOR_VECTORS {
+            let entropy = hex::decode(entropy).unwrap();
+            let last = mnemonic.rsplit(' ').next().unwrap();
+
+            let word = match entropy.len() {
+                16 => generator.checksum_word::<16>(&entropy),
+                24 => generator.checksum_word::<24>(&entropy),
+                32 => generator.checksum_word::<32>(&entropy),
+                _ => unreachable!(),
+            };
+            assert_eq!(word.unwrap(), last);
+        }
+
+        assert!(generator.checksum_word::<16>(&[0u8; 20]).is_err());
+        assert!(generator.checksum_word::<17>(&[0u8; 17]).is_err());
+    }
+
+    #[test]
+    fn generate_many_gives_independent_wallets() {
+        let mut generator = Bip39Generator::with_language(Language::English);
+        let wallets = generator.generate_many::<16>(3, Some("batch")).unwrap();
+        assert_eq!(wallets.len(), 3);
+
+        for (i, wallet) in wallets.iter().enumerate() {
+            assert_eq!(wallet.mnemonic.word_count(), 12);
+            let entropy = Entropy::<16>::try_from(&wallet.mnemonic).unwrap();
+            assert_eq!(entropy.as_bytes(), wallet.entropy.as_slice());
+            assert_eq!(Seed::from_mnemonic(&wallet.mnemonic, Some("batch")), wallet.seed);
+
+            for other in &wallets[i + 1..] {
+                assert_ne!(wallet.entropy, other.entropy);
+            }
+        }
+    }
+
+    #[test]
+    fn secure_mnemonic_rejects_empty_passphrase() {
+        let mut generator = Bip39Generator::with_language(Language::English);
+
+        let error = generator.secure_mnemonic::<16>("").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(error.to_string(), Bip39Error::EmptyPassphrase.to_string());
+
+        assert!(generator.secure_mnemonic::<16>("TREZOR").is_ok());
+    }
+
+    #[test]
+    fn accent_tolerant_matching_only_applies_to_latin_languages() {
+        // The embedded wordlist spells "ábaco" in NFKD form
+        let accented = "a\u{301}baco ".repeat(11) + "abierto";
+        let typed = "ABACO abaco abac abaco abaco abaco abaco abaco abaco abaco abaco abie";
+
+        let exact = Bip39Generator::with_language(Language::Spanish);
+        let tolerant = Bip39Generator::with_language(Language::Spanish).accent_tolerant(true);
+        assert!(!exact.validate_mnemonic(typed).unwrap());
+        assert!(tolerant.validate_mnemonic(typed).unwrap());
+        assert_eq!(
+            tolerant.recover(typed, Some("TREZOR")).unwrap().as_bytes(),
+            exact.recover(&accented, Some("TREZOR")).unwrap().as_bytes(),
+        );
+
+        // English words still have to be spelled out in full
+        let english = Bip39Generator::with_language(Language::English).accent_tolerant(true);
+        assert!(!english
+            .validate_mnemonic("aban aban aban aban aban aban aban aban aban aban aban abou")
+            .unwrap());
+    }
+
+    #[test]
+    fn bits_round_trip_for_every_entropy_size() {
+        for n in ENTROPY_SIZES {
+            // The entropy and checksum bits of an `n` byte entropy
+            let length = n * 8 + n * 8 / 32;
+            let bits = (0..length).map(|i| i % 3 == 0).collect::<Vec<bool>>();
+
+            let indices = bits_to_indices(&bits);
+            assert_eq!(indices.len(), word_count(n));
+            assert!(indices.iter().all(|&index| (index as usize) < WORDLIST_LENGTH));
+
+            assert_eq!(indices_to_bits(&indices), bits);
+        }
+    }
+
+    #[test]
+    fn bits_to_indices_reads_most_significant_bit_first() {
+        let mut bits = vec![false; 22];
+        bits[10] = true;
+        bits[11] = true;
+
+        assert_eq!(bits_to_indices(&bits), vec![1, 1024]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bits_to_indices_rejects_partial_words() {
+        bits_to_indices(&[true; 12]);
+    }
+
+    #[test]
+    fn validation_reports_every_problem() {
+        let generator = Bip39Generator::with_language(Language::English);
+
+        let error = generator
+            .mnemonic_to_entropy("abandon abandn abandon abandon abandon abandon abandom")
+            .unwrap_err();
+        let error = error.into_inner().unwrap().downcast::<Bip39Error>().unwrap();
+        assert_eq!(
+            error.problems(),
+            [
+                Bip39Error::UnknownWord("abandn".to_owned()),
+                Bip39Error::UnknownWord("abandom".to_owned()),
+                Bip39Error::InvalidWordCount(7, BITS_PER_WORD),
+            ]
+        );
+        assert_eq!(
+            error.to_string(),
+            "invalid mnemonic: `abandn` is not in the wordlist; `abandom` is not in the wordlist; 7 words, expected 12, 15, 18, 21 or 24"
+        );
+
+        // A single problem is reported on its own
+        let error = generator
+            .mnemonic_to_entropy(&["abandon"; 12].join(" "))
+            .unwrap_err();
+        assert_eq!(error.to_string(), Bip39Error::InvalidChecksum.to_string());
+    }
+
+    #[test]
+    fn compute_checksum_keeps_only_the_checksum_bits() {
+        // SHA256 of 16 zero bytes starts with 0x37, we keep 4 bits
+        assert_eq!(compute_checksum(&[0u8; 16]), Ok(0x30));
+        // SHA256 of 16 0xFF bytes starts with 0x5A, the checksum
+        // is the `0101` ending the last word "wrong"
+        assert_eq!(compute_checksum(&[0xFFu8; 16]), Ok(0x50));
+        // SHA256 of 32 zero bytes starts with 0x66, we keep all 8 bits
+        assert_eq!(compute_checksum(&[0u8; 32]), Ok(0x66));
+    }
+
+    #[test]
+    fn compute_checksum_rejects_other_entropy_sizes() {
+        for length in [0, 15, 17, 36, 64] {
+            assert_eq!(
+                compute_checksum(&vec![0u8; length]),
+                Err(Bip39Error::InvalidEntropyLength(length))
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "`appended` holds 0 bytes")]
+    fn compute_rejects_missing_checksum() {
+        Bip39Generator::with_language(Language::English).compute(16);
+    }
+
+    #[test]
+    fn compute_matches_entropy_for_every_entropy_size() {
+        for n in ENTROPY_SIZES {
+            let entropy = vec![0xA5u8; n];
+
+            let mut generator = Bip39Generator::with_language(Language::English);
+            let mnemonic = generator.from_entropy(&entropy).unwrap();
+            assert_eq!(mnemonic.word_count(), word_count(n));
+
+            assert_eq!(
+                generator.mnemonic_to_entropy(&mnemonic.to_string()).unwrap(),
+                entropy
+            );
+        }
+    }
+
+    #[test]
+    fn recover_accepts_composed_japanese_and_korean() {
+        // The wordlists are in NFKD form but a keyboard types the
+        // composed (NFC) form, e.g. "かようび" or "가격"
+        let entropy = hex::decode(TREZOR_VECTORS[3].0).unwrap();
+        for language in [Language::Japanese, Language::Korean] {
+            let mut generator = Bip39Generator::with_language(language);
+            let mnemonic = generator.from_entropy(&entropy).unwrap().to_string();
+            let typed = mnemonic.nfc().collect::<String>();
+            assert_ne!(typed, mnemonic);
+
+            assert_eq!(generator.mnemonic_to_entropy(&typed).unwrap(), entropy);
+            assert_eq!(
+                generator.recover(&typed, Some("TREZOR")).unwrap().as_bytes(),
+                generator.recover(&mnemonic, Some("TREZOR")).unwrap().as_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn detect_accepts_composed_words() {
+        let entropy = hex::decode(TREZOR_VECTORS[3].0).unwrap();
+        for language in [Language::Japanese, Language::Korean, Language::Spanish] {
+            let mut generator = Bip39Generator::with_language(language);
+            let mnemonic = generator.from_entropy(&entropy).unwrap().to_string();
+            let typed = mnemonic.nfc().collect::<String>();
+
+            assert_eq!(Language::detect(&typed), Some(language));
+        }
+    }
+
+    #[test]
+    fn mnemonic_parses_composed_words_into_wordlist_words() {
+        let entropy = hex::decode(TREZOR_VECTORS[3].0).unwrap();
+        let mut generator = Bip39Generator::with_language(Language::Japanese);
+        let mnemonic = generator.from_entropy(&entropy).unwrap();
+        let typed = mnemonic.to_string().nfc().collect::<String>();
+
+        let parsed = typed.parse::<Mnemonic>().unwrap();
+        assert_eq!(parsed.language(), Language::Japanese);
+        assert_eq!(parsed.to_string(), mnemonic.to_string());
+    }
+
+    #[test]
+    fn wordlists_of_other_sizes_set_the_bits_per_word() {
+        let words = Language::English.wordlist().lines().map(str::to_owned);
+        let (entropy, _, _) = TREZOR_VECTORS[3];
+        let entropy = hex::decode(entropy).unwrap();
+
+        // 1024 words give 10 bits, 16 bytes of entropy and 4 bits
+        // of checksum then need 14 words
+        let short = words.clone().take(1024).collect::<Vec<String>>();
+        let mut in_memory = Bip39Generator::from_wordlist(short.clone()).unwrap();
+        assert_eq!(in_memory.bits_per_word(), 10);
+        assert_eq!(in_memory.wordlist_length(), 1024);
+
+        let mnemonic = in_memory.from_entropy(&entropy).unwrap();
+        assert_eq!(mnemonic.word_count(), 14);
+        assert_eq!(mnemonic.bits_per_word(), 10);
+        assert_eq!(mnemonic.entropy_bits(), 128);
+        assert_eq!(in_memory.mnemonic_to_entropy(&mnemonic.to_string()).unwrap(), entropy);
+
+        assert_eq!(in_memory.generate_runtime(14).unwrap().0.word_count(), 14);
+        let error = in_memory.generate_runtime(12).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid mnemonic: 12 words, expected 14, 17, 20, 24 or 27"
+        );
+
+        // The builder checks a wordlist file against its bits
+        let file_name = format!("wallet-bk-recovery-short-{}.txt", std::process::id());
+        let path = std::env::temp_dir().join(file_name);
+        std::fs::write(&path, short.join("\n")).unwrap();
+
+        let mut from_file = Bip39Generator::builder()
+            .wordlist_path(&path)
+            .bits_per_word(10)
+            .build();
+        assert_eq!(from_file.wordlist_length(), 1024);
+        assert_eq!(from_file.from_entropy(&entropy).unwrap(), mnemonic);
+
+        let mut mismatched = Bip39Generator::builder().wordlist_path(&path).build();
+        assert!(mismatched.from_entropy(&entropy).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+
+        // Only a power of two of words can be indexed by bits, so
+        // a 1626 word list like old Electrum's is refused
+        let uneven = words.take(1626).collect::<Vec<String>>();
+        let error = Bip39Generator::from_wordlist(uneven).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            error.to_string(),
+            "invalid wordlist in memory: expected a power of two between 2 and 65536 \
+             words so each word holds whole bits, but found 1626"
+        );
+    }
+
+    #[test]
+    fn from_dice_hashes_the_rolls_like_coldcard() {
+        let mut generator = Bip39Generator::with_language(Language::English);
+        let rolls = (0..99).map(|i| i % 6 + 1).collect::<Vec<u8>>();
+
+        // SHA256 of "123456123456...12", the first 50 rolls
+        let mnemonic = generator.from_dice(&rolls[..50], 128).unwrap();
+        assert_eq!(
+            hex::encode(generator.mnemonic_to_entropy(&mnemonic.to_string()).unwrap()),
+            "ee72ae915a4e6ea7ccbeb8e5e5eecef2"
+        );
+
+        // All 99 rolls for 256 bits
+        let mnemonic = generator.from_dice(&rolls, 256).unwrap();
+        assert_eq!(
+            hex::encode(generator.mnemonic_to_entropy(&mnemonic.to_string()).unwrap()),
+            "5588d3630bd19f6375b7bd922457af34ea9c74f00807566a1cf808e445dc8c20"
+        );
+    }
+
+    #[test]
+    fn from_dice_rejects_too_few_or_invalid_rolls() {
+        let mut generator = Bip39Generator::with_language(Language::English);
+        let rolls = [3u8; 50];
+
+        assert!(generator.from_dice(&rolls[..49], 128).is_err());
+        assert!(generator.from_dice(&rolls, 256).is_err());
+
+        for face in [0, 7] {
+            let mut rolls = rolls;
+            rolls[10] = face;
+            let error = generator.from_dice(&rolls, 128).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                format!("invalid dice roll {}, expected 1 to 6", face)
+            );
+        }
+    }
+
+    #[test]
+    fn from_coin_flips_packs_the_flips_into_bytes() {
+        let mut generator = Bip39Generator::with_language(Language::English);
+
+        // "01" 64 times packs into 0x55 bytes, extra flips are ignored
+        let flips = "01".repeat(64) + "1111";
+        let mnemonic = generator.from_coin_flips(&flips, 128).unwrap();
+        assert_eq!(
+            generator.mnemonic_to_entropy(&mnemonic.to_string()).unwrap(),
+            vec![0x55u8; 16]
+        );
+
+        let flips = "1".repeat(8) + &"0".repeat(120);
+        let mnemonic = generator.from_coin_flips(&flips, 128).unwrap();
+        let mut expected = vec![0u8; 16];
+        expected[0] = 0xFF;
+        assert_eq!(generator.mnemonic_to_entropy(&mnemonic.to_string()).unwrap(), expected);
+    }
+
+    #[test]
+    fn from_coin_flips_rejects_too_few_or_invalid_flips() {
+        let mut generator = Bip39Generator::with_language(Language::English);
+
+        assert!(generator.from_coin_flips(&"0".repeat(127), 128).is_err());
+
+        let flips = "0".repeat(64) + "2" + &"1".repeat(63);
+        let error = generator.from_coin_flips(&flips, 128).unwrap_err();
+        assert_eq!(error.to_string(), "invalid coin flip `2`, expected `0` or `1`");
+        assert!(generator.from_coin_flips(&("H".to_owned() + &"0".repeat(127)), 128).is_err());
+    }
+
+    #[test]
+    fn mnemonic_entropy_uses_the_wordlist_it_was_made_from() {
+        let words = Language::English.wordlist().lines().map(str::to_owned);
+        let (entropy, _, _) = TREZOR_VECTORS[3];
+        let entropy = hex::decode(entropy).unwrap();
+
+        // Every word is English but at another index, looking them
+        // up in the embedded list would give other bytes
+        let file_name = format!("wallet-bk-recovery-reversed-{}.txt", std::process::id());
+        let path = std::env::temp_dir().join(file_name);
+        let reversed = words.clone().rev().collect::<Vec<String>>();
+        std::fs::write(&path, reversed.join("\n")).unwrap();
+
+        let mut from_file = Bip39Generator::new(&path);
+        let mnemonic = from_file.from_entropy(&entropy).unwrap();
+        assert_eq!(mnemonic.entropy_hex().unwrap(), hex::encode(&entropy));
+        assert_eq!(Entropy::<16>::try_from(&mnemonic).unwrap().as_bytes()[..], entropy[..]);
+        assert!(Entropy::<32>::try_from(&mnemonic).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+
+        // Indexes of 10 bits from a list of 1024 words
+        let short = words.take(1024).collect::<Vec<String>>();
+        let mut in_memory = Bip39Generator::from_wordlist(short).unwrap();
+        let mnemonic = in_memory.from_entropy(&entropy).unwrap();
+        assert_eq!(mnemonic.entropy_hex().unwrap(), hex::encode(&entropy));
+    }
+
+    #[test]
+    fn network_of_the_builder_changes_serialized_keys() {
+        let (_, mnemonic, _) = TREZOR_VECTORS[0];
+        let mainnet = Bip39Generator::builder().build();
+        let testnet = Bip39Generator::builder().network(Network::Testnet).build();
+        let seed = mainnet.recover(mnemonic, None).unwrap();
+
+        assert!(mainnet.master_xprv(&seed).unwrap().starts_with("xprv"));
+        assert!(testnet.master_xprv(&seed).unwrap().starts_with("tprv"));
+
+        // The BIP84 test vector for "abandon ... about"
+        assert_eq!(
+            mainnet.account_xpub(&seed, 84, 0).unwrap(),
+            "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs"
+        );
+        // Testnet uses coin type 1' and a vpub
+        let root = bip32::ExtendedPrivKey::new_master(seed.as_bytes()).unwrap();
+        assert_eq!(
+            testnet.account_xpub(&seed, 84, 0).unwrap(),
+            root.account_xpub(84, 1, 0, Network::Testnet).unwrap()
+        );
+        assert!(testnet.account_xpub(&seed, 84, 0).unwrap().starts_with("vpub"));
+    }
+
+    #[test]
+    fn mnemonic_runtime_gives_every_word_count() {
+        let mut generator = Bip39Generator::with_language(Language::English);
+
+        for words in [12, 15, 18, 21, 24] {
+            let mnemonic = generator.mnemonic_runtime(words).unwrap();
+            assert_eq!(mnemonic.word_count(), words);
+            assert!(generator.validate_mnemonic(&mnemonic.to_string()).unwrap());
+        }
+
+        let wallet = generator.generate_full_runtime(18, Some("TREZOR")).unwrap();
+        assert_eq!(wallet.entropy.len(), 24);
+        assert_eq!(
+            generator.recover(&wallet.mnemonic.to_string(), Some("TREZOR")).unwrap(),
+            wallet.seed
+        );
+
+        let error = generator.mnemonic_runtime(13).unwrap_err();
+        assert_eq!(error.to_string(), "invalid mnemonic: 13 words, expected 12, 15, 18, 21 or 24");
+    }
+}
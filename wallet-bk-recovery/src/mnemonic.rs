@@ -0,0 +1,310 @@
+use std::{
+    fmt,
+    io::{self, Write},
+    str::FromStr,
+};
+
+use unicode_normalization::UnicodeNormalization;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::{
+    crypto, entropy_bytes_for_words_with, entropy_from_words, Bip39Error, Bip39Generator,
+    Language, Seed, BITS_PER_WORD,
+};
+
+// A mnemonic sentence made up of words from the wordlist of
+// `language`. Keeping the words separately (instead of one
+// `String`) means we don't have to split the sentence again
+// every time we need them, and they are wiped from memory
+// when the mnemonic is dropped.
+//
+// We deliberately don't implement `Hash` (or `Ord`). A `HashMap`
+// keyed by mnemonics would run the secret words through a hasher
+// that makes no promise to wipe its state, and a fast hash of
+// the words is itself a shortcut for brute forcing them. To key
+// mnemonics, e.g. in a test harness, use `id()` instead, which
+// is the master key fingerprint wallets already show openly.
+#[derive(Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct Mnemonic {
+    words: Vec<String>,
+    #[zeroize(skip)]
+    language: Language,
+    // The bits each word stands for, the `BITS_PER_WORD` of BIP39
+    // unless the generator was built for a custom wordlist size
+    #[zeroize(skip)]
+    bits_per_word: usize,
+    // The index of each word in the wordlist the mnemonic was
+    // made from, which may not be the embedded list of our
+    // language, so `entropy()` never has to look a word up again
+    indices: Vec<u16>,
+}
+
+impl Mnemonic {
+    // The generator builds mnemonics from words it already
+    // knows are valid so it skips the validation `FromStr` does
+    pub(crate) fn new(
+        words: Vec<String>,
+        indices: Vec<u16>,
+        language: Language,
+        bits_per_word: usize,
+    ) -> Self {
+        Self {
+            words,
+            language,
+            bits_per_word,
+            indices,
+        }
+    }
+
+    // An iterator over the words of the mnemonic in order
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(String::as_str)
+    }
+
+    // The number of words, 12, 15, 18, 21 or 24 for BIP39
+    pub fn word_count(&self) -> usize {
+        self.words.len()
+    }
+
+    // The number of bits each of our words stands for, 11 for
+    // BIP39, see `Bip39GeneratorBuilder::bits_per_word()`
+    pub fn bits_per_word(&self) -> usize {
+        self.bits_per_word
+    }
+
+    // The language of the wordlist our words come from
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    // A 4 byte label for the mnemonic, to use e.g. as the key of
+    // a map instead of the words themselves. It is the BIP32
+    // fingerprint of the seed without a passphrase, so it is
+    // what wallets show as the master fingerprint, e.g. `73c5da0a`
+    // for "abandon ... about". Finding the words behind an id
+    // means running PBKDF2 for every guess, which is why we are
+    // happy to hand it out where a plain hash of the words would
+    // not be. With only 32 bits, two mnemonics can share an id
+    // (about even odds among 77000 mnemonics), so it can label
+    // mnemonics but never tell us two of them are equal. Deriving
+    // the seed takes a few milliseconds, keep the id around
+    // rather than calling this over and over.
+    pub fn id(&self) -> [u8; 4] {
+        Seed::from(self).fingerprint()
+    }
+
+    // The bits of entropy behind our words, 128, 160, 192, 224
+    // or 256. With 11 bit words one of every 33 bits is checksum
+    // so that is 32/33 of the total, but words of other sizes
+    // may end in padding, so we look the count up instead.
+    pub fn entropy_bits(&self) -> usize {
+        entropy_bytes_for_words_with(self.word_count(), self.bits_per_word).map_or(0, |n| n * 8)
+    }
+
+    // This turns our words back into the entropy they were made
+    // from and hex encodes it, the compact form of the mnemonic,
+    // e.g. 32 hex characters for 12 words. We use the indexes of
+    // the words in the wordlist the mnemonic was made from, so
+    // this also works for custom wordlists, and the checksum is
+    // checked again, so we get an error rather than wrong bytes
+    // when it doesn't match. The hex is as secret as the words,
+    // so wipe it once done with it.
+    pub fn entropy_hex(&self) -> Result<String, Bip39Error> {
+        Ok(hex::encode(self.entropy()?.as_slice()))
+    }
+
+    // The entropy behind our words, see `entropy_hex()`
+    pub(crate) fn entropy(&self) -> Result<Zeroizing<Vec<u8>>, Bip39Error> {
+        Ok(Zeroizing::new(crypto::indices_to_entropy_with(
+            &self.indices,
+            self.bits_per_word,
+        )?))
+    }
+
+    // This writes each word on its own line with its position
+    // so it is easy to write down, padding the numbers so they
+    // line up e.g.
+    //  9. foo
+    // 10. bar
+    // Writing to a buffer or file lets a GUI or log capture the
+    // listing, keep in mind it holds the secret words.
+    pub fn write_words(&self, writer: &mut impl Write) -> io::Result<()> {
+        for (index, word) in self.words().enumerate() {
+            writeln!(writer, "{:>2}. {}", index + 1, word)?;
+        }
+
+        Ok(())
+    }
+
+    // Same as `write_words()` but prints the listing to stdout
+    pub fn print_words(&self) -> io::Result<()> {
+        self.write_words(&mut io::stdout().lock())
+    }
+
+    // A coarse rating of our entropy to show to users. 128 bits
+    // is what BIP39 recommends at the least and is already far
+    // beyond brute forcing, longer mnemonics add extra margin.
+    pub fn strength_label(&self) -> &'static str {
+        match self.entropy_bits() {
+            0..=128 => "standard",
+            129..=224 => "strong",
+            _ => "very strong",
+        }
+    }
+}
+
+// With the `qr` feature we can render the mnemonic as a QR
+// code to move it to an air-gapped device by scanning it.
+// The QR code holds the sentence exactly as `to_string()`
+// prints it. The rendered output is a plain `String` which
+// we can't wipe for the caller, so it should be dropped
+// as soon as it has been shown.
+#[cfg(feature = "qr")]
+impl Mnemonic {
+    // This renders the QR code as an SVG image
+    pub fn qr_svg(&self) -> String {
+        self.qr_code()
+            .render::<qrcode::render::svg::Color<'_>>()
+            .min_dimensions(256, 256)
+            .build()
+    }
+
+    // This renders the QR code with unicode half blocks so it
+    // can be printed to a terminal, two rows of modules per line.
+    // Dark modules are printed as blanks since most terminals
+    // draw light text on a dark background.
+    pub fn qr_terminal(&self) -> String {
+        self.qr_code()
+            .render::<qrcode::render::unicode::Dense1x2>()
+            .dark_color(qrcode::render::unicode::Dense1x2::Light)
+            .light_color(qrcode::render::unicode::Dense1x2::Dark)
+            .build()
+    }
+
+    fn qr_code(&self) -> qrcode::QrCode {
+        let sentence = Zeroizing::new(self.to_string());
+
+        // Even 24 Japanese words are far below the 2953 bytes
+        // the largest QR code can hold
+        qrcode::QrCode::new(sentence.as_bytes()).expect("a mnemonic always fits in a QR code")
+    }
+}
+
+// We print the mnemonic the way the spec writes it, the words
+// joined by the separator of the language
+impl fmt::Display for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, word) in self.words.iter().enumerate() {
+            if index > 0 {
+                f.write_str(self.language.separator())?;
+            }
+            f.write_str(word)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Debug output never shows the words, so a mnemonic that ends
+// up in a log or a panic message doesn't leak the wallet. Use
+// `to_string()` or `words()` when the words are really needed.
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Mnemonic(<redacted {} words>)", self.word_count())
+    }
+}
+
+// This lets us compare a mnemonic with a sentence directly, e.g.
+// `assert_eq!(mnemonic, "abandon ... about")`. We compare word by
+// word so extra spaces, tabs or newlines between the words don't
+// matter, and each word is compared in NFKD form so a sentence
+// typed with composed accents still matches the wordlist
+// spelling. Like `==` between two mnemonics this is not constant
+// time, use `Seed::ct_eq()` where timing matters.
+impl PartialEq<str> for Mnemonic {
+    fn eq(&self, other: &str) -> bool {
+        let mut others = other.split_whitespace();
+
+        self.words().all(|word| {
+            others
+                .next()
+                .is_some_and(|other| word.nfkd().eq(other.nfkd()))
+        }) && others.next().is_none()
+    }
+}
+
+impl PartialEq<&str> for Mnemonic {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+// Parsing a mnemonic checks that every word is in the wordlist
+// and that the checksum is valid, so a `Mnemonic` is always
+// something we can safely derive a seed from.
+impl FromStr for Mnemonic {
+    type Err = Bip39Error;
+
+    fn from_str(mnemonic: &str) -> Result<Self, Self::Err> {
+        // The wordlists are in NFKD form, so we put each word in
+        // NFKD form before comparing it. Composed (NFC) Japanese
+        // or Korean, which is what most keyboards type, would
+        // otherwise never be found. The words are wiped if we
+        // return an error below.
+        let mut words = Zeroizing::new(
+            mnemonic
+                .split_whitespace()
+                .map(|word| word.nfkd().collect::<String>())
+                .collect::<Vec<String>>(),
+        );
+        let mnemonic = Zeroizing::new(words.join(" "));
+
+        // Find the language of our words. When no wordlist has
+        // all of them we pick the one with the most matching words
+        // so the error below names the word that is actually wrong,
+        // preferring the earlier language (English first) on a tie.
+        let language = Language::detect(&mnemonic).unwrap_or_else(|| {
+            Language::ALL
+                .into_iter()
+                .rev()
+                .max_by_key(|language| {
+                    let wordlist = language.wordlist();
+                    words
+                        .iter()
+                        .filter(|word| wordlist.lines().any(|entry| entry == *word))
+                        .count()
+                })
+                .unwrap_or_default()
+        });
+
+        let generator = Bip39Generator::with_language(language);
+        let word_index = generator
+            .load_word_index()
+            .expect("embedded wordlists always hold 2048 words");
+
+        // This checks the words, word count and checksum for us,
+        // so every word has an index below
+        entropy_from_words(word_index, &mnemonic)?;
+        let indices = words.iter().map(|word| word_index[word.as_str()]).collect();
+
+        Ok(Mnemonic::new(std::mem::take(&mut *words), indices, language, BITS_PER_WORD))
+    }
+}
+
+// With the `serde` feature a mnemonic is stored as its sentence
+// and parsed (so validated) again when deserialized
+#[cfg(feature = "serde")]
+impl serde::Serialize for Mnemonic {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&Zeroizing::new(self.to_string()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Mnemonic {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mnemonic = Zeroizing::new(String::deserialize(deserializer)?);
+
+        mnemonic.parse().map_err(serde::de::Error::custom)
+    }
+}
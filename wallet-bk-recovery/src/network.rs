@@ -0,0 +1,112 @@
+use std::str::FromStr;
+
+use crate::bip32::{
+    MAINNET_HRP, MAINNET_XPRV_VERSION, MAINNET_XPUB_VERSION, REGTEST_HRP, TESTNET_HRP,
+    TESTNET_XPRV_VERSION, TESTNET_XPUB_VERSION,
+};
+use crate::Bip39Error;
+
+// The byte that prefixes a private key encoded as WIF,
+// which makes it start with `5`, `K` or `L` on mainnet and
+// `9` or `c` on testnet and regtest
+pub const MAINNET_WIF_PREFIX: u8 = 0x80;
+pub const TESTNET_WIF_PREFIX: u8 = 0xEF;
+// The byte that prefixes a P2PKH public key hash before it is
+// Base58Check encoded, giving `1...` addresses on mainnet and
+// `m...` or `n...` addresses on testnet and regtest
+pub const MAINNET_P2PKH_PREFIX: u8 = 0x00;
+pub const TESTNET_P2PKH_PREFIX: u8 = 0x6F;
+// The byte that prefixes a P2SH script hash before it is
+// Base58Check encoded, giving `3...` addresses on mainnet
+// and `2...` addresses on testnet and regtest
+pub const MAINNET_P2SH_PREFIX: u8 = 0x05;
+pub const TESTNET_P2SH_PREFIX: u8 = 0xC4;
+
+// The Bitcoin network we create keys and addresses for. The
+// same seed gives the same keys on every network, only the
+// way we serialize them changes. Regtest shares the version
+// bytes and WIF prefix of testnet but has its own bech32 hrp.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Testnet,
+    Regtest,
+}
+
+impl Network {
+    // The version bytes of an extended private key, `xprv` or `tprv`
+    pub fn xprv_version(&self) -> [u8; 4] {
+        match self {
+            Network::Mainnet => MAINNET_XPRV_VERSION,
+            Network::Testnet | Network::Regtest => TESTNET_XPRV_VERSION,
+        }
+    }
+
+    // The version bytes of an extended public key, `xpub` or `tpub`
+    pub fn xpub_version(&self) -> [u8; 4] {
+        match self {
+            Network::Mainnet => MAINNET_XPUB_VERSION,
+            Network::Testnet | Network::Regtest => TESTNET_XPUB_VERSION,
+        }
+    }
+
+    // The byte that starts a WIF private key
+    pub fn wif_prefix(&self) -> u8 {
+        match self {
+            Network::Mainnet => MAINNET_WIF_PREFIX,
+            Network::Testnet | Network::Regtest => TESTNET_WIF_PREFIX,
+        }
+    }
+
+    // The byte that starts a P2PKH address
+    pub fn p2pkh_prefix(&self) -> u8 {
+        match self {
+            Network::Mainnet => MAINNET_P2PKH_PREFIX,
+            Network::Testnet | Network::Regtest => TESTNET_P2PKH_PREFIX,
+        }
+    }
+
+    // The byte that starts a P2SH address
+    pub fn p2sh_prefix(&self) -> u8 {
+        match self {
+            Network::Mainnet => MAINNET_P2SH_PREFIX,
+            Network::Testnet | Network::Regtest => TESTNET_P2SH_PREFIX,
+        }
+    }
+
+    // The coin type, the second hardened level of a BIP44 style
+    // path. SLIP-44 gives Bitcoin `0'` and every test network `1'`
+    pub fn coin_type(&self) -> u32 {
+        match self {
+            Network::Mainnet => 0,
+            Network::Testnet | Network::Regtest => 1,
+        }
+    }
+
+    // The human readable part of bech32 addresses,
+    // `bc`, `tb` or `bcrt`
+    pub fn hrp(&self) -> &'static str {
+        match self {
+            Network::Mainnet => MAINNET_HRP,
+            Network::Testnet => TESTNET_HRP,
+            Network::Regtest => REGTEST_HRP,
+        }
+    }
+}
+
+// This lets us parse a network the way Bitcoin Core names them,
+// e.g. from a command line argument. `main` and `test` are
+// accepted too.
+impl FromStr for Network {
+    type Err = Bip39Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "mainnet" | "main" | "bitcoin" => Ok(Network::Mainnet),
+            "testnet" | "test" => Ok(Network::Testnet),
+            "regtest" => Ok(Network::Regtest),
+            _ => Err(Bip39Error::UnknownNetwork(name.to_owned())),
+        }
+    }
+}
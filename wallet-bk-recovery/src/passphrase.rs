@@ -0,0 +1,48 @@
+use alloc::string::String;
+use core::fmt;
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+// The optional BIP39 passphrase that is mixed into the salt of
+// PBKDF2. Anyone with the mnemonic and the passphrase can recover
+// the wallet, so we hold it in a type that wipes it from memory
+// when dropped instead of a plain `String`. The seed functions
+// still take a `&str` which we get from `as_str()`.
+#[derive(Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct Passphrase(String);
+
+impl Passphrase {
+    // This takes ownership of the passphrase so no copy of it is left behind
+    pub fn new(passphrase: String) -> Self {
+        Self(passphrase)
+    }
+
+    // The passphrase to pass to e.g. `Bip39Generator::recover()`
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    // An empty passphrase gives the same seed as no passphrase at all
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for Passphrase {
+    fn from(passphrase: String) -> Self {
+        Passphrase::new(passphrase)
+    }
+}
+
+impl From<&str> for Passphrase {
+    fn from(passphrase: &str) -> Self {
+        Passphrase::new(String::from(passphrase))
+    }
+}
+
+// We never print the passphrase itself, not even in debug output
+impl fmt::Debug for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Passphrase(..)")
+    }
+}
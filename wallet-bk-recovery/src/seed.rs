@@ -0,0 +1,129 @@
+use std::fmt;
+
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::{bip32::ExtendedPrivKey, crypto, Bip39Error, Mnemonic, SEED_LENGTH};
+
+// The 512 bit seed we derive from a mnemonic and passphrase.
+// This is what BIP32 turns into the master key of a wallet,
+// so it is wiped from memory when dropped.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct Seed([u8; SEED_LENGTH]);
+
+impl Seed {
+    // This wraps the 64 bytes we got out of PBKDF2
+    pub fn new(bytes: [u8; SEED_LENGTH]) -> Self {
+        Self(bytes)
+    }
+
+    // This copies a seed out of a slice which must be exactly 64 bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Bip39Error> {
+        let bytes = <[u8; SEED_LENGTH]>::try_from(bytes)
+            .map_err(|_| Bip39Error::InvalidSeedLength(bytes.len()))?;
+
+        Ok(Self(bytes))
+    }
+
+    // The raw bytes of the seed
+    pub fn as_bytes(&self) -> &[u8; SEED_LENGTH] {
+        &self.0
+    }
+
+    // The seed as 128 lowercase hex characters
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    // This parses a seed written as 128 hex characters
+    pub fn from_hex(seed: &str) -> Result<Self, Bip39Error> {
+        let bytes = Zeroizing::new(
+            hex::decode(seed).map_err(|error| Bip39Error::InvalidHex(error.to_string()))?,
+        );
+
+        Seed::from_bytes(&bytes)
+    }
+
+    // This derives the seed of a mnemonic with an optional
+    // passphrase, using the 2048 iterations of BIP39
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: Option<&str>) -> Self {
+        let sentence = Zeroizing::new(mnemonic.to_string());
+
+        Seed(
+            crypto::seed(&sentence, passphrase)
+                .expect("PBKDF2 always runs `ITERATION_COUNT` times"),
+        )
+    }
+
+    // The standard BIP32 wallet fingerprint of this seed, the
+    // first 4 bytes of the HASH160 of the master public key. It
+    // can't be turned back into the seed so it is safe to show as
+    // an 8 hex character label, e.g. `73c5da0a` for the "abandon
+    // ... about" test mnemonic. Wallets show the same value as
+    // the master fingerprint of a key origin like `[73c5da0a/84'/0'/0']`.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        // HMAC-SHA512 gives an invalid master key with a chance
+        // of about 1 in 2^128, BIP32 has no fallback for that
+        ExtendedPrivKey::new_master(&self.0)
+            .expect("the master key of a 64 byte seed is valid")
+            .fingerprint()
+    }
+
+    // This compares two seeds in constant time, it looks at
+    // every byte no matter where the first difference is so
+    // the time taken doesn't leak how much of a seed matched
+    pub fn ct_eq(&self, other: &Seed) -> bool {
+        self.0.ct_eq(&other.0).into()
+    }
+}
+
+// A mnemonic always has a seed, this derives it without a
+// passphrase, use `Seed::from_mnemonic()` to pass one
+impl From<&Mnemonic> for Seed {
+    fn from(mnemonic: &Mnemonic) -> Self {
+        Seed::from_mnemonic(mnemonic, None)
+    }
+}
+
+// Comparing seeds with `==` (or `assert_eq!`) goes through
+// `ct_eq()` so there is no way to compare them naively
+impl PartialEq for Seed {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other)
+    }
+}
+
+impl Eq for Seed {}
+
+// Unlike `Display`, which has to be asked for on purpose, debug
+// output is easy to end up in a log by accident so it never
+// shows the bytes of the seed
+impl fmt::Debug for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Seed(<redacted>)")
+    }
+}
+
+// Seeds are printed as hex
+impl fmt::Display for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+// With the `serde` feature a seed is stored as hex
+#[cfg(feature = "serde")]
+impl serde::Serialize for Seed {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&Zeroizing::new(self.to_hex()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Seed {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let seed = Zeroizing::new(String::deserialize(deserializer)?);
+
+        Seed::from_hex(&seed).map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,258 @@
+use rand_core::RngCore;
+use zeroize::Zeroizing;
+
+use crate::{system_rng, Bip39Error};
+
+// Shamir secret sharing splits a secret into `shares` pieces
+// so that any `threshold` of them give the secret back while
+// fewer give away nothing about it. We share every byte of
+// the secret separately using a random polynomial over
+// GF(256), the field of bytes also used by AES. The polynomial
+// of each byte has the secret byte as its constant term and
+// `threshold - 1` random coefficients, and each share holds
+// that polynomial evaluated at its own non-zero `x`.
+//
+// Every share is laid out as
+//
+//     [threshold, x, y_0, y_1, ..., y_n]
+//
+// keeping the threshold with the share lets `combine()` tell
+// when it wasn't given enough shares instead of silently
+// returning the wrong secret.
+
+// This function multiplies two elements of GF(256) reducing by
+// the AES polynomial x^8 + x^4 + x^3 + x + 1. It always runs
+// through all 8 bits so the time taken doesn't depend on
+// the bytes of the secret.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+
+    for _ in 0..8 {
+        // Add `a` when the lowest bit of `b` is set
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        // Multiply `a` by x, reducing when it overflows
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (0x1B & carry);
+        b >>= 1;
+    }
+
+    product
+}
+
+// Every non-zero element of GF(256) satisfies a^255 = 1, so
+// a^254 is its inverse. There is no inverse of zero but
+// `combine()` never asks for one since all `x` are distinct.
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut power = a;
+    let mut exponent = 254u8;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = gf_mul(result, power);
+        }
+        power = gf_mul(power, power);
+        exponent >>= 1;
+    }
+
+    result
+}
+
+// This function splits `secret` into `shares` shares of which
+// any `threshold` are needed to get it back. It panics when
+// `threshold` is zero or larger than `shares`, since no set of
+// shares could then give the secret back.
+pub fn split(secret: &[u8], threshold: u8, shares: u8) -> Vec<Vec<u8>> {
+    assert!(threshold > 0, "the threshold must be at least 1");
+    assert!(
+        threshold <= shares,
+        "the threshold can't be larger than the number of shares"
+    );
+
+    let mut rng = system_rng();
+
+    // Each share starts with the threshold and its `x`,
+    // we hand out `x` = 1, 2, ... since `x` = 0 is the secret
+    let mut output = (1..=shares)
+        .map(|x| {
+            let mut share = Vec::with_capacity(secret.len() + 2);
+            share.push(threshold);
+            share.push(x);
+            share
+        })
+        .collect::<Vec<Vec<u8>>>();
+
+    // The coefficients of the polynomial for the current byte,
+    // the constant term is the secret byte itself
+    let mut coefficients = Zeroizing::new(vec![0u8; threshold as usize]);
+
+    for byte in secret {
+        coefficients[0] = *byte;
+        rng.fill_bytes(&mut coefficients[1..]);
+
+        for share in output.iter_mut() {
+            let x = share[1];
+
+            // Evaluate the polynomial at `x` using Horner's method
+            let y = coefficients
+                .iter()
+                .rev()
+                .fold(0u8, |y, coefficient| gf_mul(y, x) ^ coefficient);
+
+            share.push(y);
+        }
+    }
+
+    output
+}
+
+// This function gives back the secret from shares made by
+// `split()`. We need at least as many shares as the threshold
+// they were made with, all of the same length and with
+// different `x`. Extra shares are fine, we only use as many
+// as the threshold.
+pub fn combine(shares: &[Vec<u8>]) -> Result<Vec<u8>, Bip39Error> {
+    let first = shares
+        .first()
+        .ok_or(Bip39Error::NotEnoughShares(1, 0))?;
+
+    if first.len() < 2 {
+        return Err(Bip39Error::InvalidShare(String::from("share is too short")));
+    }
+
+    let threshold = first[0];
+
+    for share in shares {
+        if share.len() != first.len() {
+            return Err(Bip39Error::InvalidShare(String::from(
+                "shares have different lengths",
+            )));
+        }
+        if share[0] != threshold {
+            return Err(Bip39Error::InvalidShare(String::from(
+                "shares have different thresholds",
+            )));
+        }
+        if share[1] == 0 {
+            return Err(Bip39Error::InvalidShare(String::from("share has x = 0")));
+        }
+    }
+
+    if threshold == 0 {
+        return Err(Bip39Error::InvalidShare(String::from("threshold is 0")));
+    }
+
+    if shares.len() < threshold as usize {
+        return Err(Bip39Error::NotEnoughShares(threshold as usize, shares.len()));
+    }
+
+    let shares = &shares[..threshold as usize];
+
+    // Two shares with the same `x` are the same point so
+    // they don't tell us enough to find the polynomial
+    for (index, share) in shares.iter().enumerate() {
+        if shares[..index].iter().any(|other| other[1] == share[1]) {
+            return Err(Bip39Error::InvalidShare(format!(
+                "duplicate share with x = {}",
+                share[1]
+            )));
+        }
+    }
+
+    // Lagrange interpolation at x = 0 gives us the constant term
+    // of each polynomial, which is our secret byte. In GF(256)
+    // addition and subtraction are both XOR, so the basis
+    // polynomial of share i at 0 is the product of
+    // x_j / (x_j ^ x_i) over every other share j.
+    let basis = shares
+        .iter()
+        .map(|share| {
+            shares
+                .iter()
+                .filter(|other| other[1] != share[1])
+                .fold(1u8, |basis, other| {
+                    gf_mul(basis, gf_mul(other[1], gf_inv(other[1] ^ share[1])))
+                })
+        })
+        .collect::<Vec<u8>>();
+
+    let secret = (2..first.len())
+        .map(|position| {
+            shares
+                .iter()
+                .zip(basis.iter())
+                .fold(0u8, |byte, (share, basis)| {
+                    byte ^ gf_mul(share[position], *basis)
+                })
+        })
+        .collect();
+
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"abandon abandon abandon about";
+
+    #[test]
+    fn any_three_of_five_shares_give_the_secret_back() {
+        let shares = split(SECRET, 3, 5);
+        assert_eq!(shares.len(), 5);
+        assert!(shares.iter().all(|share| share.len() == SECRET.len() + 2));
+
+        for subset in [[0, 1, 2], [0, 2, 4], [1, 3, 4], [4, 3, 2]] {
+            let subset = subset.map(|index| shares[index].clone());
+            assert_eq!(combine(&subset).unwrap(), SECRET);
+        }
+
+        // Extra shares are ignored
+        assert_eq!(combine(&shares).unwrap(), SECRET);
+    }
+
+    #[test]
+    fn two_of_three_of_five_shares_are_not_enough() {
+        let shares = split(SECRET, 3, 5);
+
+        assert_eq!(combine(&shares[..2]), Err(Bip39Error::NotEnoughShares(3, 2)));
+        assert_eq!(combine(&[]), Err(Bip39Error::NotEnoughShares(1, 0)));
+    }
+
+    #[test]
+    fn combine_rejects_shares_that_do_not_belong_together() {
+        let shares = split(SECRET, 2, 3);
+
+        // The same share twice has the same `x`
+        let duplicate = [shares[0].clone(), shares[0].clone()];
+        assert!(matches!(combine(&duplicate), Err(Bip39Error::InvalidShare(_))));
+
+        let mut short = shares[1].clone();
+        short.pop();
+        let mismatched = [shares[0].clone(), short];
+        assert!(matches!(combine(&mismatched), Err(Bip39Error::InvalidShare(_))));
+    }
+
+    #[test]
+    fn threshold_one_shares_each_hold_the_secret() {
+        let shares = split(SECRET, 1, 3);
+
+        for share in &shares {
+            // Every polynomial is the constant secret byte
+            assert_eq!(&share[2..], SECRET);
+            assert_eq!(combine(std::slice::from_ref(share)).unwrap(), SECRET);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the threshold must be at least 1")]
+    fn split_panics_on_threshold_zero() {
+        split(SECRET, 0, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "the threshold can't be larger than the number of shares")]
+    fn split_panics_on_threshold_above_shares() {
+        split(SECRET, 4, 3);
+    }
+}
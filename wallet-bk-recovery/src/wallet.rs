@@ -0,0 +1,29 @@
+use std::fmt;
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{Mnemonic, Seed};
+
+// Everything a single generation produces, see
+// `Bip39Generator::generate_full()`. The entropy is the compact
+// backup of the mnemonic, the seed is what BIP32 turns into the
+// keys of the wallet. All three are secret and wiped from memory
+// when the wallet is dropped.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct GeneratedWallet {
+    pub entropy: Vec<u8>,
+    pub mnemonic: Mnemonic,
+    pub seed: Seed,
+}
+
+// The mnemonic and seed already redact themselves, the entropy
+// is a plain `Vec<u8>` so we only print its length
+impl fmt::Debug for GeneratedWallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GeneratedWallet")
+            .field("entropy", &format_args!("<redacted {} bytes>", self.entropy.len()))
+            .field("mnemonic", &self.mnemonic)
+            .field("seed", &self.seed)
+            .finish()
+    }
+}
@@ -0,0 +1,34 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{Bip39Generator, Language, Mnemonic};
+
+// These are the functions we export to JavaScript when building
+// with the `wasm` feature. There is no filesystem in the browser
+// so they all use the embedded English wordlist, and errors are
+// thrown as JavaScript exceptions.
+
+// This generates a new English mnemonic of `word_count` words,
+// 12, 15, 18, 21 or 24 with the BIP39 wordlist
+#[wasm_bindgen]
+pub fn generate_mnemonic(word_count: u32) -> Result<String, JsError> {
+    let mut generator = Bip39Generator::with_language(Language::English);
+    let mnemonic = generator.mnemonic_runtime(word_count as usize)?;
+
+    Ok(mnemonic.to_string())
+}
+
+// This validates the mnemonic and derives its 64 byte seed,
+// pass an empty string when there is no passphrase
+#[wasm_bindgen]
+pub fn mnemonic_to_seed(mnemonic: &str, passphrase: &str) -> Result<Vec<u8>, JsError> {
+    let generator = Bip39Generator::with_language(Language::English);
+    let seed = generator.recover(mnemonic, Some(passphrase))?;
+
+    Ok(seed.as_bytes().to_vec())
+}
+
+// This checks the words and checksum of a mnemonic
+#[wasm_bindgen]
+pub fn validate(mnemonic: &str) -> bool {
+    mnemonic.parse::<Mnemonic>().is_ok()
+}